@@ -0,0 +1,189 @@
+//! This module provides access to the EDGAR archives at `www.sec.gov/Archives/edgar/`,
+//! which hold the indexes of every filing and the filings themselves.
+pub mod complete_submission;
+pub mod daily_index;
+pub mod download;
+pub mod filing_index;
+pub mod full_index;
+
+use crate::error::EDGARError;
+use async_compression::tokio::bufread::{DeflateDecoder, GzipDecoder};
+use bytes::Bytes;
+use futures::{stream::BoxStream, TryStreamExt};
+use std::{
+    io::{self, BufRead, Cursor},
+    pin::Pin,
+};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio_util::io::StreamReader;
+
+/// The formats the index files of the archives are served in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compression {
+    /// The uncompressed file, such as `form.idx`.
+    Plain,
+    /// The gzip compressed file, such as `form.gz`.
+    Gzip,
+    /// The zip archive holding the file, such as `form.zip`.
+    Zip,
+}
+impl Compression {
+    /// The extension of the file in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Plain => "idx",
+            Self::Gzip => "gz",
+            Self::Zip => "zip",
+        }
+    }
+}
+
+/// A reader of a file of the archives, read as it is downloaded.
+pub(crate) type ArchiveReader = Pin<Box<dyn AsyncBufRead + Send>>;
+
+/// Returns a reader of `body`, the body of a [streaming response](crate::client::EdgarStreamingResponse).
+pub(crate) fn body_reader(body: BoxStream<'static, Result<Vec<u8>, EDGARError>>) -> ArchiveReader {
+    Box::pin(StreamReader::new(
+        body.map_ok(Bytes::from).map_err(io::Error::other),
+    ))
+}
+/// Returns a reader of the decompressed `reader`, whose format is detected from its first bytes.
+/// Decompression happens as the reader is read, so neither the compressed nor the decompressed file is held in memory as a whole.
+///
+/// Only the first file of a zip archive is read.
+pub(crate) async fn decompress(mut reader: ArchiveReader) -> Result<ArchiveReader, EDGARError> {
+    let mut magic = vec![];
+    (&mut reader).take(4).read_to_end(&mut magic).await?;
+    let is_gzip = magic.starts_with(&[0x1f, 0x8b]);
+    let is_zip = magic.starts_with(b"PK\x03\x04");
+    let reader: ArchiveReader = Box::pin(Cursor::new(magic).chain(reader));
+    if is_gzip {
+        return Ok(Box::pin(BufReader::new(GzipDecoder::new(reader))));
+    }
+    if is_zip {
+        return unzip_first(reader).await;
+    }
+    Ok(reader)
+}
+/// Reads the first entry of a zip archive from its local file header.
+async fn unzip_first(mut reader: ArchiveReader) -> Result<ArchiveReader, EDGARError> {
+    let mut header = [0; 30];
+    reader.read_exact(&mut header).await?;
+    let u16_at = |i: usize| u16::from_le_bytes([header[i], header[i + 1]]);
+    let compressed_size = u32::from_le_bytes([header[18], header[19], header[20], header[21]]);
+    let name_and_extra = u16_at(26) as u64 + u16_at(28) as u64;
+    tokio::io::copy(
+        &mut (&mut reader).take(name_and_extra),
+        &mut tokio::io::sink(),
+    )
+    .await?;
+    match u16_at(8) {
+        // The entries that follow, and the central directory, are not part of the file.
+        0 => Ok(Box::pin(reader.take(compressed_size as u64))),
+        8 => Ok(Box::pin(BufReader::new(DeflateDecoder::new(reader)))),
+        method => Err(EDGARError::UnsupportedCompression { method }),
+    }
+}
+/// Reads a line as Latin-1, which EDGAR uses for its index files, without the line ending.
+/// Returns None at the end of `reader`.
+pub(crate) fn read_latin1_line<R: BufRead>(reader: &mut R) -> Option<Result<String, EDGARError>> {
+    let mut line = vec![];
+    match reader.read_until(b'\n', &mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(Ok(latin1_line(&line))),
+        Err(e) => Some(Err(e.into())),
+    }
+}
+/// Reads a line as Latin-1 like [read_latin1_line], from a reader of a download.
+pub(crate) async fn read_latin1_line_async<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Option<Result<String, EDGARError>> {
+    let mut line = vec![];
+    match reader.read_until(b'\n', &mut line).await {
+        Ok(0) => None,
+        Ok(_) => Some(Ok(latin1_line(&line))),
+        Err(e) => Some(Err(e.into())),
+    }
+}
+fn latin1_line(line: &[u8]) -> String {
+    let line: String = line.iter().map(|&b| b as char).collect();
+    line.trim_end_matches(['\r', '\n']).to_string()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder},
+        Compression as Level,
+    };
+    use futures::StreamExt;
+    use std::io::Write;
+
+    pub(crate) fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(vec![], Level::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+    /// A zip archive holding `data` as its only, deflated, file. The central directory is left out because it is not read.
+    pub(crate) fn zip(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut encoder = DeflateEncoder::new(vec![], Level::default());
+        encoder.write_all(data).unwrap();
+        let deflated = encoder.finish().unwrap();
+        let mut zip = b"PK\x03\x04".to_vec();
+        zip.extend([20, 0, 0, 0, 8, 0, 0, 0, 0, 0]);
+        zip.extend([0; 4]);
+        zip.extend((deflated.len() as u32).to_le_bytes());
+        zip.extend((data.len() as u32).to_le_bytes());
+        zip.extend((name.len() as u16).to_le_bytes());
+        zip.extend([0, 0]);
+        zip.extend(name.as_bytes());
+        zip.extend(deflated);
+        zip
+    }
+    /// Streams `body` in chunks of 3 bytes, as a download would.
+    pub(crate) fn chunked(body: &[u8]) -> BoxStream<'static, Result<Vec<u8>, EDGARError>> {
+        let chunks: Vec<_> = body.chunks(3).map(|c| Ok(c.to_vec())).collect();
+        futures::stream::iter(chunks).boxed()
+    }
+    async fn read_all(body: Vec<u8>) -> String {
+        let mut text = String::new();
+        let mut reader = decompress(body_reader(chunked(&body))).await.unwrap();
+        reader.read_to_string(&mut text).await.unwrap();
+        text
+    }
+    #[tokio::test]
+    async fn archives_decompress() {
+        let data = "CIK|Company Name\n831001|CITIGROUP INC\n";
+        assert_eq!(read_all(data.as_bytes().to_vec()).await, data);
+        assert_eq!(read_all(gzip(data.as_bytes())).await, data);
+        assert_eq!(read_all(zip("master.idx", data.as_bytes())).await, data);
+        let mut stored = zip("master.idx", data.as_bytes());
+        stored[8] = 0;
+        stored.truncate(30 + "master.idx".len());
+        stored[18..22].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        stored.extend(data.as_bytes());
+        stored.extend(b"PK\x01\x02central directory");
+        assert_eq!(read_all(stored).await, data);
+        assert_eq!(read_all(vec![]).await, "")
+    }
+    #[test]
+    fn archives_read_latin1_line() {
+        let mut reader = Cursor::new(b"SOCI\xc9T\xc9 G\xc9N\xc9RALE\r\nnext".to_vec());
+        assert_eq!(
+            read_latin1_line(&mut reader).unwrap().unwrap(),
+            "SOCIÉTÉ GÉNÉRALE"
+        );
+        assert_eq!(read_latin1_line(&mut reader).unwrap().unwrap(), "next");
+        assert!(read_latin1_line(&mut reader).is_none())
+    }
+    #[tokio::test]
+    async fn archives_read_latin1_line_async() {
+        let mut reader = body_reader(chunked(b"SOCI\xc9T\xc9 G\xc9N\xc9RALE\r\nnext"));
+        let mut lines = vec![];
+        while let Some(line) = read_latin1_line_async(&mut reader).await {
+            lines.push(line.unwrap());
+        }
+        assert_eq!(lines, vec!["SOCIÉTÉ GÉNÉRALE", "next"])
+    }
+}
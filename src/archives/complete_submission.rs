@@ -0,0 +1,857 @@
+//! This module parses the complete submission text file of a filing, `{accession number}.txt`, which holds every document of the filing.
+//!
+//! The file is SGML: an `<SEC-HEADER>` that describes the filing and its parties, followed by a `<DOCUMENT>` block per document.
+//! ```txt
+//! <SEC-DOCUMENT>0000831001-23-000114.txt : 20230804
+//! <SEC-HEADER>0000831001-23-000114.hdr.sgml : 20230804
+//! <ACCEPTANCE-DATETIME>20230804163146
+//! ACCESSION NUMBER:        0000831001-23-000114
+//! CONFORMED SUBMISSION TYPE:    10-Q
+//! FILER:
+//!     COMPANY DATA:
+//!         COMPANY CONFORMED NAME:            CITIGROUP INC
+//! <!-- snip -->
+//! </SEC-HEADER>
+//! <DOCUMENT>
+//! <TYPE>GRAPHIC
+//! <SEQUENCE>3
+//! <FILENAME>c-20230630_g1.jpg
+//! <TEXT>
+//! begin 644 c-20230630_g1.jpg
+//! <!-- snip -->
+//! ```
+//! Documents are read one at a time by a [SubmissionReader], so only the largest document of a submission is ever held in memory,
+//! and [SubmissionReader::next_document_to] writes the body of a document elsewhere without holding it at all.
+//! Binary documents, which are uuencoded, are decoded.
+//! ```
+//! use sec_edgar::archives::complete_submission::SubmissionReader;
+//! use std::{fs::File, io::BufReader};
+//! fn some_func() {
+//!     let file = File::open("0000831001-23-000114.txt").unwrap();
+//!     let reader = SubmissionReader::new(BufReader::new(file)).unwrap();
+//!     println!("{}", reader.header().submission_type);
+//!     for document in reader {
+//!         let document = document.unwrap();
+//!         println!("{} {:?} {}", document.document_type, document.file_name, document.body.len());
+//!     }
+//! }
+//! ```
+
+use crate::archives::{
+    body_reader, filing_index::folder_path, full_index::parse_index_date, read_latin1_line,
+    read_latin1_line_async, ArchiveReader,
+};
+use crate::client::EdgarClient;
+use crate::edgar_query::{
+    company_info::{Address, AddressType, FiscalYearEnd, FormerName},
+    edgar_query_builder::add_leading_zeros_to_cik,
+    filing_content_value::AccessionNumber,
+};
+use crate::error::EDGARError;
+use chrono::{NaiveDate, NaiveDateTime};
+use reqwest::header::HeaderMap;
+use std::io::{BufRead, Write};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+/// The part a [Party] plays in a filing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PartyRole {
+    /// The company the filing is filed for, as in `FILER:`.
+    Filer,
+    /// The company the filing is about, such as the target of a tender offer, as in `SUBJECT COMPANY:`.
+    SubjectCompany,
+    /// The company that filed a filing about the subject company, as in `FILED BY:`.
+    FiledBy,
+    /// The insider of an ownership report, as in `REPORTING-OWNER:`.
+    ReportingOwner,
+    /// The company whose securities an ownership report is about, as in `ISSUER:`.
+    Issuer,
+    /// Any other section of the header, as written in it.
+    Other(String),
+}
+impl From<&str> for PartyRole {
+    fn from(section: &str) -> Self {
+        match section {
+            "FILER" => Self::Filer,
+            "SUBJECT COMPANY" => Self::SubjectCompany,
+            "FILED BY" => Self::FiledBy,
+            "REPORTING-OWNER" => Self::ReportingOwner,
+            "ISSUER" => Self::Issuer,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// A company or person named in the header of a submission.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Party {
+    /// See [PartyRole]
+    pub role: PartyRole,
+    /// The name of the party.
+    pub conformed_name: Option<String>,
+    /// The CIK with leading zeros.
+    pub cik: Option<String>,
+    /// The Standard Industrial Classification code.
+    pub sic: Option<u16>,
+    /// The description of the SIC code, such as `NATIONAL COMMERCIAL BANKS`.
+    pub sic_description: Option<String>,
+    /// The IRS employer identification number.
+    pub irs_number: Option<String>,
+    /// The state or country code of incorporation.
+    pub state_of_incorporation: Option<String>,
+    /// See [FiscalYearEnd]
+    pub fiscal_year_end: Option<FiscalYearEnd>,
+    /// The form type the party filed under, such as `10-Q`.
+    pub form_type: Option<String>,
+    /// The act the party filed under, such as `1934 Act`.
+    pub act: Option<String>,
+    /// The SEC file number of the party, such as `001-09924`.
+    pub file_number: Option<String>,
+    /// The film number of the filing for the party.
+    pub film_number: Option<String>,
+    /// See [Address]
+    pub addresses: Vec<Address>,
+    /// See [FormerName]
+    pub former_names: Vec<FormerName>,
+}
+impl Party {
+    fn new(role: PartyRole) -> Self {
+        Self {
+            role,
+            conformed_name: None,
+            cik: None,
+            sic: None,
+            sic_description: None,
+            irs_number: None,
+            state_of_incorporation: None,
+            fiscal_year_end: None,
+            form_type: None,
+            act: None,
+            file_number: None,
+            film_number: None,
+            addresses: vec![],
+            former_names: vec![],
+        }
+    }
+    /// Sets the field `key` of the `block` of the header, such as `COMPANY DATA` or `BUSINESS ADDRESS`.
+    fn set(&mut self, block: &str, key: &str, text: &str) {
+        let value = Some(text.to_string());
+        match block {
+            "BUSINESS ADDRESS" | "MAIL ADDRESS" => {
+                let Some(address) = self.addresses.last_mut() else {
+                    return;
+                };
+                match key {
+                    "STREET 1" => address.street1 = value,
+                    "STREET 2" => address.street2 = value,
+                    "CITY" => address.city = value,
+                    "STATE" => address.state = value,
+                    "ZIP" => address.zip = value,
+                    "BUSINESS PHONE" | "PHONE" => address.phone = value,
+                    _ => {}
+                }
+            }
+            "FORMER COMPANY" | "FORMER NAME" => {
+                let Some(former_name) = self.former_names.last_mut() else {
+                    return;
+                };
+                match key {
+                    "FORMER CONFORMED NAME" => former_name.name = text.to_string(),
+                    "DATE OF NAME CHANGE" => former_name.date = parse_index_date(text),
+                    _ => {}
+                }
+            }
+            _ => match key {
+                "COMPANY CONFORMED NAME" => self.conformed_name = value,
+                "CENTRAL INDEX KEY" => self.cik = Some(add_leading_zeros_to_cik(text)),
+                "STANDARD INDUSTRIAL CLASSIFICATION" => {
+                    // Such as `NATIONAL COMMERCIAL BANKS [6021]`.
+                    let (description, code) = match text.rsplit_once('[') {
+                        Some((d, c)) => (d.trim(), c.trim_end_matches(']').trim().parse().ok()),
+                        None => (text, None),
+                    };
+                    self.sic = code;
+                    self.sic_description = Some(description.to_string()).filter(|d| !d.is_empty());
+                }
+                "IRS NUMBER" => self.irs_number = value,
+                "STATE OF INCORPORATION" => self.state_of_incorporation = value,
+                "FISCAL YEAR END" => self.fiscal_year_end = text.parse().ok(),
+                "FORM TYPE" => self.form_type = value,
+                "SEC ACT" => self.act = value,
+                "SEC FILE NUMBER" => self.file_number = value,
+                "FILM NUMBER" => self.film_number = value,
+                _ => {}
+            },
+        }
+    }
+    /// Starts the `block` of the header, which adds an address or a former name for the blocks that hold one.
+    fn start_block(&mut self, block: &str) {
+        let address_type = match block {
+            "BUSINESS ADDRESS" => AddressType::Business,
+            "MAIL ADDRESS" => AddressType::Mailing,
+            "FORMER COMPANY" | "FORMER NAME" => {
+                self.former_names.push(FormerName {
+                    name: String::new(),
+                    date: None,
+                });
+                return;
+            }
+            _ => return,
+        };
+        self.addresses.push(Address {
+            address_type,
+            street1: None,
+            street2: None,
+            city: None,
+            state: None,
+            zip: None,
+            phone: None,
+        });
+    }
+}
+
+/// The `<SEC-HEADER>` of a submission.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmissionHeader {
+    /// See [AccessionNumber]
+    pub accession_number: AccessionNumber,
+    /// The time, in Eastern Time, at which EDGAR accepted the submission.
+    pub acceptance_datetime: Option<NaiveDateTime>,
+    /// The form type of the submission, such as `10-Q`.
+    pub submission_type: String,
+    /// The number of documents in the submission that are made public.
+    pub public_document_count: Option<u32>,
+    /// The end of the period the submission reports on.
+    pub period_of_report: Option<NaiveDate>,
+    /// The date the submission was filed.
+    pub filed_as_of_date: Option<NaiveDate>,
+    /// The date the submission was last changed.
+    pub date_as_of_change: Option<NaiveDate>,
+    /// The items reported in the submission, such as `Results of Operations and Financial Condition` for an 8-K.
+    pub items: Vec<String>,
+    /// The members of a group filing, such as the affiliates filing a Schedule 13D together.
+    pub group_members: Vec<String>,
+    /// See [Party]
+    pub parties: Vec<Party>,
+}
+impl SubmissionHeader {
+    /// The parties that filed the submission.
+    pub fn filers(&self) -> Vec<&Party> {
+        self.parties_with_role(&PartyRole::Filer)
+    }
+    /// The company the submission is about, if it is not the filer.
+    pub fn subject_company(&self) -> Option<&Party> {
+        self.parties_with_role(&PartyRole::SubjectCompany)
+            .into_iter()
+            .next()
+    }
+    /// The party that filed a submission about the subject company.
+    pub fn filed_by(&self) -> Option<&Party> {
+        self.parties_with_role(&PartyRole::FiledBy)
+            .into_iter()
+            .next()
+    }
+    /// The insiders of an ownership report.
+    pub fn reporting_owners(&self) -> Vec<&Party> {
+        self.parties_with_role(&PartyRole::ReportingOwner)
+    }
+    /// The company whose securities an ownership report is about.
+    pub fn issuer(&self) -> Option<&Party> {
+        self.parties_with_role(&PartyRole::Issuer)
+            .into_iter()
+            .next()
+    }
+    fn parties_with_role(&self, role: &PartyRole) -> Vec<&Party> {
+        self.parties.iter().filter(|p| p.role == *role).collect()
+    }
+    /// Parses the lines of the header, which are `KEY: value` pairs nested with tabs.
+    fn parse(lines: &[String]) -> Result<Self, EDGARError> {
+        let mut accession_number = None;
+        let mut acceptance_datetime = None;
+        let mut submission_type = String::new();
+        let mut public_document_count = None;
+        let mut dates = [None; 3];
+        let mut items = vec![];
+        let mut group_members = vec![];
+        let mut parties: Vec<Party> = vec![];
+        let mut block = String::new();
+        let mut in_party = false;
+        for line in lines {
+            if let Some(datetime) = line.strip_prefix("<ACCEPTANCE-DATETIME>") {
+                acceptance_datetime =
+                    NaiveDateTime::parse_from_str(datetime.trim(), "%Y%m%d%H%M%S").ok();
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let depth = line.chars().take_while(|c| *c == '\t').count();
+            let (key, value) = (key.trim(), value.trim());
+            match depth {
+                0 if value.is_empty() => {
+                    parties.push(Party::new(key.into()));
+                    in_party = true;
+                    block.clear();
+                }
+                0 => {
+                    in_party = false;
+                    match key {
+                        "ACCESSION NUMBER" => accession_number = Some(value.parse()?),
+                        "CONFORMED SUBMISSION TYPE" => submission_type = value.to_string(),
+                        "PUBLIC DOCUMENT COUNT" => public_document_count = value.parse().ok(),
+                        "CONFORMED PERIOD OF REPORT" => dates[0] = parse_index_date(value),
+                        "FILED AS OF DATE" => dates[1] = parse_index_date(value),
+                        "DATE AS OF CHANGE" => dates[2] = parse_index_date(value),
+                        "ITEM INFORMATION" => items.push(value.to_string()),
+                        "GROUP MEMBERS" => group_members.push(value.to_string()),
+                        _ => {}
+                    }
+                }
+                _ => {
+                    let Some(party) = parties.last_mut().filter(|_| in_party) else {
+                        continue;
+                    };
+                    if value.is_empty() {
+                        block = key.to_string();
+                        party.start_block(key);
+                    } else {
+                        party.set(&block, key, value);
+                    }
+                }
+            }
+        }
+        let [period_of_report, filed_as_of_date, date_as_of_change] = dates;
+        Ok(Self {
+            accession_number: accession_number.ok_or(EDGARError::SubmissionHeaderNotFound)?,
+            acceptance_datetime,
+            submission_type,
+            public_document_count,
+            period_of_report,
+            filed_as_of_date,
+            date_as_of_change,
+            items,
+            group_members,
+            parties,
+        })
+    }
+}
+
+/// A `<DOCUMENT>` of a submission.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmissionDocument {
+    /// The type of the document, such as `10-Q`, `EX-31.1` or `GRAPHIC`.
+    pub document_type: String,
+    /// The position of the document in the submission.
+    pub sequence: Option<u32>,
+    /// The name of the file, such as `c-20230630.htm`.
+    pub file_name: Option<String>,
+    /// The description of the document.
+    pub description: Option<String>,
+    /// The contents of the file. Uuencoded documents are decoded,
+    /// and the `<XML>`, `<XBRL>` or `<PDF>` tags wrapping some documents are removed.
+    pub body: Vec<u8>,
+}
+impl SubmissionDocument {
+    /// The body as text, decoded as UTF-8.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// A whole submission, with every document in memory. See [SubmissionReader] to read large submissions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    /// See [SubmissionHeader]
+    pub header: SubmissionHeader,
+    /// See [SubmissionDocument]
+    pub documents: Vec<SubmissionDocument>,
+}
+impl Submission {
+    /// Parses the whole submission read from `reader`.
+    pub fn parse<R: BufRead>(reader: R) -> Result<Self, EDGARError> {
+        let reader = SubmissionReader::new(reader)?;
+        let header = reader.header().clone();
+        Ok(Self {
+            header,
+            documents: reader.collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Reads the header of a submission, then its documents one at a time.
+///
+/// Each document is returned with its body, or its body is written to a writer with [SubmissionReader::next_document_to].
+#[derive(Debug)]
+pub struct SubmissionReader<R> {
+    reader: R,
+    header: SubmissionHeader,
+}
+impl<R: BufRead> SubmissionReader<R> {
+    /// Reads the header of the submission read from `reader`.
+    ///
+    /// Returns [EDGARError::SubmissionHeaderNotFound] if there is no header, or it has no accession number.
+    pub fn new(mut reader: R) -> Result<Self, EDGARError> {
+        let mut lines = HeaderLines::default();
+        while let Some(line) = read_latin1_line(&mut reader) {
+            if let Some(header) = lines.push(line?) {
+                return Ok(Self {
+                    reader,
+                    header: header?,
+                });
+            }
+        }
+        Err(EDGARError::SubmissionHeaderNotFound)
+    }
+    /// See [SubmissionHeader]
+    pub fn header(&self) -> &SubmissionHeader {
+        &self.header
+    }
+    /// Reads the next document, whose body is written to `writer` instead of being held in memory.
+    /// The `body` of the returned document is left empty.
+    pub fn next_document_to<W: Write>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<Option<SubmissionDocument>, EDGARError> {
+        let mut parser = DocumentParser::default();
+        let mut line = vec![];
+        let mut body = vec![];
+        loop {
+            line.clear();
+            if self.reader.read_until(b'\n', &mut line)? == 0 {
+                return Ok(parser.finish());
+            }
+            let document = parser.push(&line, &mut body);
+            writer.write_all(&body)?;
+            body.clear();
+            if let Some(document) = document {
+                return Ok(document);
+            }
+        }
+    }
+}
+impl<R: BufRead> Iterator for SubmissionReader<R> {
+    type Item = Result<SubmissionDocument, EDGARError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut body = vec![];
+        let document = self.next_document_to(&mut body).transpose()?;
+        Some(document.map(|document| SubmissionDocument { body, ..document }))
+    }
+}
+
+/// Reads the header of a submission, then its documents one at a time, as the submission is downloaded.
+///
+/// See [get_complete_submission].
+#[derive(Debug)]
+pub struct AsyncSubmissionReader<R> {
+    reader: R,
+    header: SubmissionHeader,
+}
+impl<R: AsyncBufRead + Unpin> AsyncSubmissionReader<R> {
+    /// Reads the header of the submission read from `reader`.
+    ///
+    /// Returns [EDGARError::SubmissionHeaderNotFound] if there is no header, or it has no accession number.
+    pub async fn new(mut reader: R) -> Result<Self, EDGARError> {
+        let mut lines = HeaderLines::default();
+        while let Some(line) = read_latin1_line_async(&mut reader).await {
+            if let Some(header) = lines.push(line?) {
+                return Ok(Self {
+                    reader,
+                    header: header?,
+                });
+            }
+        }
+        Err(EDGARError::SubmissionHeaderNotFound)
+    }
+    /// See [SubmissionHeader]
+    pub fn header(&self) -> &SubmissionHeader {
+        &self.header
+    }
+    /// Reads the next document with its body. Returns None after the last document.
+    pub async fn next_document(&mut self) -> Result<Option<SubmissionDocument>, EDGARError> {
+        let mut body = vec![];
+        let document = self.next_document_to(&mut body).await?;
+        Ok(document.map(|document| SubmissionDocument { body, ..document }))
+    }
+    /// Reads the next document, whose body is written to `writer` instead of being held in memory.
+    /// The `body` of the returned document is left empty.
+    pub async fn next_document_to<W: AsyncWrite + Unpin>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<Option<SubmissionDocument>, EDGARError> {
+        let mut parser = DocumentParser::default();
+        let mut line = vec![];
+        let mut body = vec![];
+        loop {
+            line.clear();
+            if self.reader.read_until(b'\n', &mut line).await? == 0 {
+                return Ok(parser.finish());
+            }
+            let document = parser.push(&line, &mut body);
+            writer.write_all(&body).await?;
+            body.clear();
+            if let Some(document) = document {
+                return Ok(document);
+            }
+        }
+    }
+}
+
+/// Collects the lines of the `<SEC-HEADER>`, and parses them once it ends.
+#[derive(Debug, Default)]
+struct HeaderLines {
+    lines: Vec<String>,
+    in_header: bool,
+}
+impl HeaderLines {
+    /// Returns the header once `line` ends it.
+    fn push(&mut self, line: String) -> Option<Result<SubmissionHeader, EDGARError>> {
+        if line.starts_with("<SEC-HEADER>") || line.starts_with("<IMS-HEADER>") {
+            self.in_header = true;
+        } else if line.starts_with("</SEC-HEADER>") || line.starts_with("</IMS-HEADER>") {
+            return Some(SubmissionHeader::parse(&self.lines));
+        } else if self.in_header {
+            self.lines.push(line);
+        }
+        None
+    }
+}
+
+/// Parses a `<DOCUMENT>`, fed one line at a time.
+#[derive(Debug, Default)]
+struct DocumentParser {
+    document: Option<SubmissionDocument>,
+    body: Option<BodyDecoder>,
+}
+impl DocumentParser {
+    /// Reads `line`, appending the bytes of the body it holds to `body`.
+    ///
+    /// Returns `Some(None)` at the end of the submission, and `Some(document)` once the document ends.
+    fn push(&mut self, line: &[u8], body: &mut Vec<u8>) -> Option<Option<SubmissionDocument>> {
+        let trimmed = trim_line_end(line);
+        let Some(document) = &mut self.document else {
+            match trimmed {
+                b"<DOCUMENT>" => {
+                    self.document = Some(SubmissionDocument {
+                        document_type: String::new(),
+                        sequence: None,
+                        file_name: None,
+                        description: None,
+                        body: vec![],
+                    })
+                }
+                b"</SEC-DOCUMENT>" => return Some(None),
+                _ => {}
+            }
+            return None;
+        };
+        if let Some(decoder) = &mut self.body {
+            return match decoder.push(line, body) {
+                true => Some(self.document.take()),
+                false => None,
+            };
+        }
+        let text: String = trimmed.iter().map(|&b| b as char).collect();
+        if text == "<TEXT>" {
+            self.body = Some(BodyDecoder::default());
+            return None;
+        }
+        if text == "</DOCUMENT>" {
+            return Some(self.document.take());
+        }
+        let (tag, value) = text.strip_prefix('<').and_then(|t| t.split_once('>'))?;
+        let value = value.trim().to_string();
+        match tag {
+            "TYPE" => document.document_type = value,
+            "SEQUENCE" => document.sequence = value.parse().ok(),
+            "FILENAME" => document.file_name = Some(value),
+            "DESCRIPTION" => document.description = Some(value),
+            _ => {}
+        }
+        None
+    }
+    /// The document cut off by the end of the file, if any.
+    fn finish(self) -> Option<SubmissionDocument> {
+        self.document
+    }
+}
+
+/// Decodes the lines between `<TEXT>` and `</TEXT>`.
+#[derive(Debug, Default)]
+struct BodyDecoder {
+    wrapper: Option<&'static [u8]>,
+    held: Option<Vec<u8>>,
+    uuencoded: bool,
+    started: bool,
+}
+impl BodyDecoder {
+    /// Appends the bytes `line` holds to `body`. Returns true once the text ends.
+    fn push(&mut self, line: &[u8], body: &mut Vec<u8>) -> bool {
+        let trimmed = trim_line_end(line);
+        if trimmed == b"</TEXT>" {
+            return true;
+        }
+        if !self.started {
+            if trimmed.is_empty() {
+                return false;
+            }
+            if self.wrapper.is_none() {
+                self.wrapper = match trimmed {
+                    b"<XML>" => Some(b"</XML>"),
+                    b"<XBRL>" => Some(b"</XBRL>"),
+                    b"<PDF>" => Some(b"</PDF>"),
+                    _ => None,
+                };
+                if self.wrapper.is_some() {
+                    return false;
+                }
+            }
+            self.started = true;
+            if trimmed.starts_with(b"begin ") {
+                self.uuencoded = true;
+                return false;
+            }
+        }
+        // The closing tag of the wrapper is only dropped if it is the last line of the text.
+        if let Some(held) = self.held.take() {
+            body.extend(held);
+        }
+        if self.uuencoded {
+            match trimmed {
+                b"end" => self.uuencoded = false,
+                _ => uudecode_line(trimmed, body),
+            }
+        } else if Some(trimmed) == self.wrapper {
+            self.held = Some(line.to_vec());
+        } else {
+            body.extend(line);
+        }
+        false
+    }
+}
+fn trim_line_end(line: &[u8]) -> &[u8] {
+    let end = line
+        .iter()
+        .rposition(|b| !matches!(b, b'\r' | b'\n'))
+        .map_or(0, |i| i + 1);
+    &line[..end]
+}
+/// Decodes a line of uuencoded data, whose first character holds the number of bytes it encodes.
+fn uudecode_line(line: &[u8], out: &mut Vec<u8>) {
+    let Some((&first, rest)) = line.split_first() else {
+        return;
+    };
+    let len = (first.wrapping_sub(b' ') & 0x3f) as usize;
+    let mut decoded = Vec::with_capacity(len + 2);
+    for group in rest.chunks(4) {
+        let mut sextets = [0u8; 4];
+        for (sextet, c) in sextets.iter_mut().zip(group) {
+            *sextet = c.wrapping_sub(b' ') & 0x3f;
+        }
+        decoded.push(sextets[0] << 2 | sextets[1] >> 4);
+        decoded.push(sextets[1] << 4 | sextets[2] >> 2);
+        decoded.push(sextets[2] << 6 | sextets[3]);
+    }
+    // Encoders may strip the trailing spaces of a line, which stand for zeros.
+    decoded.resize(len, 0);
+    out.extend(decoded);
+}
+
+/// Get the complete submission text file of the filing `accession_number` of the company with `cik`.
+///
+/// The submission is parsed as it is downloaded, so it is never held in memory as a whole.
+/// The body of a large document can be written elsewhere with [AsyncSubmissionReader::next_document_to].
+/// ```
+/// use sec_edgar::{archives::complete_submission::get_complete_submission, edgar::edgar_client};
+/// async fn some_func() {
+///     let client = edgar_client().unwrap();
+///     let accession_number = "0000831001-23-000114".parse().unwrap();
+///     let mut reader = get_complete_submission(client, "831001", &accession_number)
+///         .await
+///         .unwrap();
+///     while let Some(document) = reader.next_document().await.unwrap() {
+///         println!("{} {:?}", document.document_type, document.file_name);
+///     }
+/// }
+/// ```
+pub async fn get_complete_submission(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+) -> Result<AsyncSubmissionReader<ArchiveReader>, EDGARError> {
+    let folder = folder_path(cik, accession_number);
+    let url = client
+        .endpoints()
+        .www_url(&format!("{folder}/{}.txt", accession_number.value))?;
+    let res = client.get_streaming(&url, HeaderMap::new()).await?;
+    AsyncSubmissionReader::new(body_reader(res.body)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+
+    const SUBMISSION: &[u8] =
+        include_bytes!("../../tests/fixtures/complete_submission/0000831001-23-000114.txt");
+
+    #[test]
+    fn complete_submission_header() {
+        let reader = SubmissionReader::new(SUBMISSION).unwrap();
+        let header = reader.header();
+        assert_eq!(header.accession_number.value, "0000831001-23-000114");
+        assert_eq!(
+            header.acceptance_datetime.unwrap().to_string(),
+            "2023-08-04 16:31:46"
+        );
+        assert_eq!(header.submission_type, "10-Q");
+        assert_eq!(header.public_document_count, Some(4));
+        assert_eq!(
+            header.period_of_report,
+            NaiveDate::from_ymd_opt(2023, 6, 30)
+        );
+        let filers = header.filers();
+        assert_eq!(filers.len(), 1);
+        let citigroup = filers[0];
+        assert_eq!(citigroup.conformed_name.as_deref(), Some("CITIGROUP INC"));
+        assert_eq!(citigroup.cik.as_deref(), Some("0000831001"));
+        assert_eq!(citigroup.sic, Some(6021));
+        assert_eq!(
+            citigroup.sic_description.as_deref(),
+            Some("NATIONAL COMMERCIAL BANKS")
+        );
+        assert_eq!(citigroup.fiscal_year_end.unwrap().month, 12);
+        assert_eq!(citigroup.file_number.as_deref(), Some("001-09924"));
+        assert_eq!(citigroup.addresses.len(), 2);
+        assert_eq!(citigroup.addresses[0].address_type, AddressType::Business);
+        assert_eq!(citigroup.addresses[0].phone.as_deref(), Some("2125591000"));
+        assert_eq!(citigroup.addresses[1].phone, None);
+        assert_eq!(citigroup.former_names.len(), 2);
+        assert_eq!(citigroup.former_names[1].name, "TRAVELERS INC");
+        assert_eq!(
+            citigroup.former_names[1].date,
+            NaiveDate::from_ymd_opt(1994, 1, 3)
+        );
+        assert!(header.subject_company().is_none())
+    }
+    #[test]
+    fn complete_submission_subject_company_and_items() {
+        let header = "<SEC-HEADER>0001104659-23-079633.hdr.sgml : 20230710\n\
+            ACCESSION NUMBER:\t\t0001104659-23-079633\n\
+            CONFORMED SUBMISSION TYPE:\tSC 13G/A\n\
+            ITEM INFORMATION:\t\tOther Events\n\
+            GROUP MEMBERS:\t\tBERKSHIRE HATHAWAY INC\n\
+            \n\
+            SUBJECT COMPANY:\t\n\
+            \n\
+            \tCOMPANY DATA:\t\n\
+            \t\tCOMPANY CONFORMED NAME:\t\t\tAPPLE INC\n\
+            \t\tCENTRAL INDEX KEY:\t\t\t320193\n\
+            \n\
+            FILED BY:\t\t\n\
+            \n\
+            \tCOMPANY DATA:\t\n\
+            \t\tCOMPANY CONFORMED NAME:\t\t\tBERKSHIRE HATHAWAY INC\n\
+            \t\tCENTRAL INDEX KEY:\t\t\t0001067983\n\
+            \t\tSTANDARD INDUSTRIAL CLASSIFICATION:\t\t\t[6331]\n\
+            </SEC-HEADER>\n";
+        let mut reader = SubmissionReader::new(header.as_bytes()).unwrap();
+        let header = reader.header();
+        assert_eq!(header.items, vec!["Other Events"]);
+        assert_eq!(header.group_members, vec!["BERKSHIRE HATHAWAY INC"]);
+        assert_eq!(
+            header.subject_company().unwrap().cik.as_deref(),
+            Some("0000320193")
+        );
+        let filed_by = header.filed_by().unwrap();
+        assert_eq!(filed_by.sic, Some(6331));
+        assert_eq!(filed_by.sic_description, None);
+        assert!(header.filers().is_empty());
+        assert!(reader.next().is_none());
+        assert!(matches!(
+            SubmissionReader::new("<SEC-DOCUMENT>\n".as_bytes()),
+            Err(EDGARError::SubmissionHeaderNotFound)
+        ))
+    }
+    #[test]
+    fn complete_submission_documents() {
+        let submission = Submission::parse(SUBMISSION).unwrap();
+        let documents = submission.documents;
+        assert_eq!(documents.len(), 4);
+        assert_eq!(documents[0].document_type, "10-Q");
+        assert_eq!(documents[0].sequence, Some(1));
+        assert_eq!(documents[0].file_name.as_deref(), Some("c-20230630.htm"));
+        assert!(documents[0].text().starts_with("<?xml"));
+        assert!(documents[0].text().ends_with("</html>\n"));
+        assert_eq!(
+            documents[1].text(),
+            "<html>\n<body>Certification</body>\n</html>\n"
+        );
+        assert_eq!(
+            documents[1].description.as_deref(),
+            Some("CERTIFICATION OF CEO")
+        );
+        let mut graphic: Vec<u8> = (0..=255).collect();
+        graphic.extend(b"GRAPHIC END");
+        assert_eq!(documents[2].body, graphic);
+        assert_eq!(
+            documents[3].text(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<xs:schema/>\n"
+        )
+    }
+    #[test]
+    fn complete_submission_document_to_writer() {
+        let documents = Submission::parse(SUBMISSION).unwrap().documents;
+        let mut reader = SubmissionReader::new(SUBMISSION).unwrap();
+        for document in documents {
+            let mut body = vec![];
+            let streamed = reader.next_document_to(&mut body).unwrap().unwrap();
+            assert_eq!(streamed.file_name, document.file_name);
+            assert!(streamed.body.is_empty());
+            assert_eq!(body, document.body);
+        }
+        assert!(reader.next_document_to(&mut vec![]).unwrap().is_none())
+    }
+    #[test]
+    fn complete_submission_uudecode_line() {
+        let mut out = vec![];
+        uudecode_line(b"#0V%T", &mut out);
+        assert_eq!(out, b"Cat");
+        // Trailing spaces stripped by the encoder.
+        out.clear();
+        uudecode_line(b"!``", &mut out);
+        uudecode_line(b"!", &mut out);
+        assert_eq!(out, [0, 0])
+    }
+    #[tokio::test]
+    async fn complete_submission_from_archives() {
+        let server = TestServer::start().route(
+            "/Archives/edgar/data/831001/000083100123000114/0000831001-23-000114.txt",
+            vec![TestResponse::ok("text/plain", SUBMISSION)],
+        );
+        let client = client(&server);
+        let accession_number = "0000831001-23-000114".parse().unwrap();
+        let mut reader = get_complete_submission(client, "0000831001", &accession_number)
+            .await
+            .unwrap();
+        assert_eq!(reader.header().submission_type, "10-Q");
+        let first = reader.next_document().await.unwrap().unwrap();
+        assert_eq!(first.file_name.as_deref(), Some("c-20230630.htm"));
+        let mut certification = vec![];
+        let second = reader
+            .next_document_to(&mut certification)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.document_type, "EX-31.1");
+        assert!(second.body.is_empty());
+        assert_eq!(
+            certification,
+            b"<html>\n<body>Certification</body>\n</html>\n"
+        );
+        let mut remaining = 0;
+        while reader.next_document().await.unwrap().is_some() {
+            remaining += 1;
+        }
+        assert_eq!(remaining, 2)
+    }
+}
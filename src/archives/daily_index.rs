@@ -0,0 +1,233 @@
+//! This module provides the daily indexes of the filings made on each business day, at `Archives/edgar/daily-index/{year}/QTR{quarter}/`.
+//!
+//! Each business day has the same four indexes as a [full index](crate::archives::full_index), named after the date, such as `master.20230804.idx`.
+//! EDGAR does not accept filings on weekends and federal holidays, so there is no index for those days.
+//! ```
+//! use sec_edgar::{
+//!     archives::{
+//!         daily_index::{get_daily_index, previous_business_day},
+//!         full_index::IndexFile,
+//!     },
+//!     edgar::edgar_client,
+//! };
+//! use futures::StreamExt;
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let today = chrono::Local::now().date_naive();
+//!     let date = previous_business_day(today);
+//!     if let Some(mut index) = get_daily_index(client, date, IndexFile::Master).await.unwrap() {
+//!         while let Some(entry) = index.next().await {
+//!             println!("{}", entry.unwrap().path);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::archives::full_index::{stream_index, IndexEntry, IndexFile};
+use crate::client::EdgarClient;
+use crate::error::EDGARError;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+/// The federal holidays observed in `year`, in order.
+/// A holiday that falls on a Saturday is observed on the Friday before, and one that falls on a Sunday on the Monday after,
+/// so New Year's Day of the following year may be observed on December 31 of `year`.
+///
+/// The current holidays are applied to every year. Closures for special events, such as national days of mourning, are not included.
+/// ```
+/// use chrono::NaiveDate;
+/// use sec_edgar::archives::daily_index::federal_holidays;
+/// let holidays = federal_holidays(2023);
+/// assert_eq!(holidays.len(), 11);
+/// assert_eq!(holidays[0], NaiveDate::from_ymd_opt(2023, 1, 2).unwrap());
+/// ```
+pub fn federal_holidays(year: i32) -> Vec<NaiveDate> {
+    let fixed = |y: i32, month: u32, day: u32| observed(NaiveDate::from_ymd_opt(y, month, day));
+    let nth = |month: u32, weekday: Weekday, n: u8| {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+    };
+    let last_monday_of_may = nth(5, Weekday::Mon, 5).or_else(|| nth(5, Weekday::Mon, 4));
+    let mut holidays = vec![
+        fixed(year, 1, 1),
+        nth(1, Weekday::Mon, 3),
+        nth(2, Weekday::Mon, 3),
+        last_monday_of_may,
+        fixed(year, 7, 4),
+        nth(9, Weekday::Mon, 1),
+        nth(10, Weekday::Mon, 2),
+        fixed(year, 11, 11),
+        nth(11, Weekday::Thu, 4),
+        fixed(year, 12, 25),
+        fixed(year + 1, 1, 1),
+    ];
+    if year >= 2021 {
+        holidays.push(fixed(year, 6, 19));
+    }
+    let mut holidays: Vec<NaiveDate> = holidays
+        .into_iter()
+        .flatten()
+        .filter(|d| d.year() == year)
+        .collect();
+    holidays.sort();
+    holidays
+}
+fn observed(date: Option<NaiveDate>) -> Option<NaiveDate> {
+    let date = date?;
+    match date.weekday() {
+        Weekday::Sat => date.pred_opt(),
+        Weekday::Sun => date.succ_opt(),
+        _ => Some(date),
+    }
+}
+
+/// Whether EDGAR accepts filings on `date`, which is neither a weekend nor a [federal holiday](federal_holidays).
+pub fn is_business_day(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+        && !federal_holidays(date.year()).contains(&date)
+}
+
+/// The business days from `start` to `end`, both included.
+/// ```
+/// use chrono::NaiveDate;
+/// use sec_edgar::archives::daily_index::business_days;
+/// let date = |d| NaiveDate::from_ymd_opt(2023, 7, d).unwrap();
+/// let days: Vec<_> = business_days(date(1), date(9)).collect();
+/// assert_eq!(days, vec![date(3), date(5), date(6), date(7)]);
+/// ```
+pub fn business_days(start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    start
+        .iter_days()
+        .take_while(move |d| *d <= end)
+        .filter(|d| is_business_day(*d))
+}
+
+/// The last business day before `date`.
+pub fn previous_business_day(date: NaiveDate) -> NaiveDate {
+    let mut day = date - Duration::days(1);
+    while !is_business_day(day) {
+        day -= Duration::days(1);
+    }
+    day
+}
+
+/// Get the `file` index of the filings made on `date`.
+///
+/// Returns None if EDGAR has no index for `date`, which is the case for weekends, holidays and days it was closed.
+/// The entries are parsed as the index is downloaded.
+pub async fn get_daily_index(
+    client: EdgarClient,
+    date: NaiveDate,
+    file: IndexFile,
+) -> Result<Option<BoxStream<'static, Result<IndexEntry, EDGARError>>>, EDGARError> {
+    let url = client.endpoints().www_url(&format!(
+        "Archives/edgar/daily-index/{}/QTR{}/{}.{}.idx",
+        date.year(),
+        date.month0() / 3 + 1,
+        file.name(),
+        date.format("%Y%m%d")
+    ))?;
+    match stream_index(client, &url, file).await {
+        Ok(index) => Ok(Some(index)),
+        Err(EDGARError::UnexpectedStatus { status: 404 }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Streams the entries of the `file` index of every [business day](business_days) from `start` to `end`, both included, in order.
+/// Days without an index are left out.
+///
+/// Each index is requested once the entries of the previous one have been read.
+pub fn get_daily_indexes(
+    client: EdgarClient,
+    start: NaiveDate,
+    end: NaiveDate,
+    file: IndexFile,
+) -> BoxStream<'static, Result<IndexEntry, EDGARError>> {
+    let days: Vec<NaiveDate> = business_days(start, end).collect();
+    stream::iter(days)
+        .then(move |date| get_daily_index(client.clone(), date, file))
+        .map_ok(|index| index.unwrap_or_else(|| stream::empty().boxed()))
+        .try_flatten()
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+    #[test]
+    fn daily_index_federal_holidays() {
+        assert_eq!(
+            federal_holidays(2023),
+            vec![
+                date(2023, 1, 2),
+                date(2023, 1, 16),
+                date(2023, 2, 20),
+                date(2023, 5, 29),
+                date(2023, 6, 19),
+                date(2023, 7, 4),
+                date(2023, 9, 4),
+                date(2023, 10, 9),
+                date(2023, 11, 10),
+                date(2023, 11, 23),
+                date(2023, 12, 25),
+            ]
+        );
+        // New Year's Day 2022 fell on a Saturday.
+        assert!(federal_holidays(2021).contains(&date(2021, 12, 31)));
+        assert!(!federal_holidays(2022).contains(&date(2021, 12, 31)));
+        assert!(!federal_holidays(2020).contains(&date(2020, 6, 19)))
+    }
+    #[test]
+    fn daily_index_previous_business_day() {
+        // The Monday after Thanksgiving and the weekend.
+        assert_eq!(
+            previous_business_day(date(2023, 11, 27)),
+            date(2023, 11, 24)
+        );
+        assert_eq!(
+            previous_business_day(date(2023, 11, 24)),
+            date(2023, 11, 22)
+        );
+        assert_eq!(previous_business_day(date(2024, 1, 2)), date(2023, 12, 29))
+    }
+    #[tokio::test]
+    async fn daily_index_skips_missing_days() {
+        let server = TestServer::start()
+            .route(
+                "/Archives/edgar/daily-index/2023/QTR3/master.20230804.idx",
+                vec![TestResponse::ok(
+                    "text/plain",
+                    include_bytes!("../../tests/fixtures/daily_index/master.20230804.idx"),
+                )],
+            )
+            .route(
+                "/Archives/edgar/daily-index/2023/QTR3/master.20230807.idx",
+                vec![TestResponse::status(403)],
+            );
+        let client = client(&server);
+        let entries = get_daily_indexes(
+            client.clone(),
+            date(2023, 8, 3),
+            date(2023, 8, 6),
+            IndexFile::Master,
+        )
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].cik, "0000831001");
+        assert_eq!(entries[0].date_filed, date(2023, 8, 4));
+        // Only the 3rd and the 4th were requested, the weekend was skipped.
+        assert_eq!(server.requests().len(), 2);
+        let failed = get_daily_index(client, date(2023, 8, 7), IndexFile::Master).await;
+        assert!(matches!(
+            failed.map(|_| ()),
+            Err(EDGARError::UnexpectedStatus { status: 403 })
+        ))
+    }
+}
@@ -0,0 +1,558 @@
+//! This module downloads the documents of filings, and their complete submission text files, from the archives.
+//!
+//! The body is written to the destination as it arrives, so large filings are never held in memory.
+//! Every request goes through the rate limiter of the client, and a download that is cut short is resumed with a `Range` request.
+//! ```
+//! use sec_edgar::{
+//!     archives::download::{download_to_path, Download},
+//!     edgar::edgar_client,
+//!     edgar_query::filing_content_value::AccessionNumber,
+//! };
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let accession_number: AccessionNumber = "0000831001-23-000114".parse().unwrap();
+//!     let download = Download::complete_submission(client.endpoints(), "831001", &accession_number)
+//!         .unwrap()
+//!         .set_progress(|p| println!("{} of {:?} bytes", p.downloaded, p.total));
+//!     let summary = download_to_path(client, &download, "0000831001-23-000114.txt")
+//!         .await
+//!         .unwrap();
+//!     println!("{}", summary.sha256);
+//! }
+//! ```
+
+use crate::archives::filing_index::folder_path;
+use crate::client::{endpoints::Endpoints, EdgarClient, EdgarStreamingResponse};
+use crate::edgar_query::filing_content_value::AccessionNumber;
+use crate::error::EDGARError;
+use futures::StreamExt;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE,
+        RANGE,
+    },
+    StatusCode,
+};
+use sha2::{Digest, Sha256};
+use std::{fmt, io, path::Path, sync::Arc};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{AsyncReadExt, AsyncWrite, AsyncWriteExt},
+};
+use url::Url;
+
+/// How far a download has gone, passed to the progress callback of a [Download] after every chunk.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// The number of bytes downloaded so far, including those already on disk when the download was resumed.
+    pub downloaded: u64,
+    /// The size of the file, if EDGAR sent it or it was set with [Download::set_expected_size].
+    pub total: Option<u64>,
+}
+
+/// What was downloaded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DownloadSummary {
+    /// The size of the file.
+    pub bytes: u64,
+    /// The SHA-256 of the file, in lowercase hexadecimal.
+    pub sha256: String,
+    /// The number of bytes that were already on disk when the download started.
+    pub resumed_from: u64,
+}
+
+/// A file to download from EDGAR, and how to check it.
+/// ```
+/// use sec_edgar::{archives::download::Download, client::endpoints::Endpoints};
+/// let accession_number = "0000831001-23-000114".parse().unwrap();
+/// let download = Download::document(&Endpoints::default(), "0000831001", &accession_number, "c-20230630.htm")
+///     .unwrap()
+///     .set_expected_size(16086546);
+/// assert_eq!(
+///     download.url().as_str(),
+///     "https://www.sec.gov/Archives/edgar/data/831001/000083100123000114/c-20230630.htm"
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Download {
+    url: Url,
+    expected_size: Option<u64>,
+    expected_sha256: Option<String>,
+    progress: Option<Arc<dyn Fn(Progress) + Send + Sync>>,
+}
+impl Download {
+    /// Instantiates a [Download] of `url`.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            expected_size: None,
+            expected_sha256: None,
+            progress: None,
+        }
+    }
+    /// The document `file_name` of the filing `accession_number` of the company with `cik`.
+    pub fn document(
+        endpoints: &Endpoints,
+        cik: &str,
+        accession_number: &AccessionNumber,
+        file_name: &str,
+    ) -> Result<Self, EDGARError> {
+        let folder = folder_path(cik, accession_number);
+        Ok(Self::new(
+            endpoints.www_url(&format!("{folder}/{file_name}"))?,
+        ))
+    }
+    /// The complete submission text file of the filing `accession_number` of the company with `cik`,
+    /// which holds every document of the filing.
+    pub fn complete_submission(
+        endpoints: &Endpoints,
+        cik: &str,
+        accession_number: &AccessionNumber,
+    ) -> Result<Self, EDGARError> {
+        let file_name = format!("{}.txt", accession_number.value);
+        Self::document(endpoints, cik, accession_number, &file_name)
+    }
+    /// Fails the download with [EDGARError::SizeMismatch] unless the file is `size` bytes long.
+    pub fn set_expected_size(mut self, size: u64) -> Self {
+        self.expected_size = Some(size);
+        self
+    }
+    /// Fails the download with [EDGARError::ChecksumMismatch] unless the SHA-256 of the file is `sha256`, in hexadecimal.
+    pub fn set_expected_sha256(mut self, sha256: &str) -> Self {
+        self.expected_sha256 = Some(sha256.trim().to_lowercase());
+        self
+    }
+    /// Calls `progress` after every chunk that is written.
+    pub fn set_progress<F: Fn(Progress) + Send + Sync + 'static>(mut self, progress: F) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+    /// The URL of the file.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+}
+impl fmt::Debug for Download {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Download")
+            .field("url", &self.url)
+            .field("expected_size", &self.expected_size)
+            .field("expected_sha256", &self.expected_sha256)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Downloads `download` into `writer`.
+///
+/// If the connection drops, the rest of the file is requested with a `Range` request,
+/// up to the maximum number of attempts of the [RetryPolicy](crate::client::retry::RetryPolicy) of the client.
+pub async fn download<W: AsyncWrite + Unpin + Send + ?Sized>(
+    client: EdgarClient,
+    download: &Download,
+    writer: &mut W,
+) -> Result<DownloadSummary, EDGARError> {
+    download_from(client, download, writer, 0, Sha256::new())
+        .await?
+        .ok_or(EDGARError::UnexpectedStatus { status: 416 })
+}
+
+/// Downloads `download` to the file at `path`.
+///
+/// If the file exists, the download resumes after its last byte with a `Range` request.
+/// A file that is already complete is only checked. A file that cannot be resumed, such as one larger than the remote file, is downloaded again from the start.
+pub async fn download_to_path<P: AsRef<Path>>(
+    client: EdgarClient,
+    download: &Download,
+    path: P,
+) -> Result<DownloadSummary, EDGARError> {
+    let mut hasher = Sha256::new();
+    let mut offset = 0;
+    if let Ok(mut existing) = File::open(path.as_ref()).await {
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            let read = existing.read(&mut buf).await?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buf[..read]);
+            offset += read as u64;
+        }
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path.as_ref())
+        .await?;
+    if let Some(summary) =
+        download_from(client.clone(), download, &mut file, offset, hasher).await?
+    {
+        return Ok(summary);
+    }
+    file.set_len(0).await?;
+    self::download(client, download, &mut file).await
+}
+
+/// Downloads the rest of `download` after the `resumed_from` bytes already written.
+/// None if the range cannot be satisfied because those bytes are not the start of the remote file.
+async fn download_from<W: AsyncWrite + Unpin + Send + ?Sized>(
+    client: EdgarClient,
+    download: &Download,
+    writer: &mut W,
+    resumed_from: u64,
+    mut hasher: Sha256,
+) -> Result<Option<DownloadSummary>, EDGARError> {
+    let mut offset = resumed_from;
+    let mut total = download.expected_size;
+    let mut attempt = 1;
+    loop {
+        let res = match client
+            .get_streaming(download.url(), range_headers(offset)?)
+            .await
+        {
+            Err(EDGARError::UnexpectedStatus { status: 416 }) if offset > 0 => {
+                match remote_size(&client, download).await? {
+                    // The file on disk is already complete.
+                    Some(size) if size == offset => break,
+                    _ => return Ok(None),
+                }
+            }
+            res => res?,
+        };
+        let remote_total = response_total(&res);
+        total = total.or(remote_total);
+        // A server that ignores the range sends the whole file again.
+        let mut skip = match res.status {
+            StatusCode::PARTIAL_CONTENT => 0,
+            _ => offset,
+        };
+        let mut body = res.body;
+        let mut failure = None;
+        while let Some(chunk) = body.next().await {
+            let chunk = match chunk {
+                Ok(c) => c,
+                Err(e) => {
+                    failure = Some(e);
+                    break;
+                }
+            };
+            let start = skip.min(chunk.len() as u64) as usize;
+            skip -= start as u64;
+            let chunk = &chunk[start..];
+            if chunk.is_empty() {
+                continue;
+            }
+            writer.write_all(chunk).await?;
+            hasher.update(chunk);
+            offset += chunk.len() as u64;
+            if let Some(progress) = &download.progress {
+                progress(Progress {
+                    downloaded: offset,
+                    total,
+                });
+            }
+        }
+        let cut_short = remote_total.is_some_and(|t| offset < t);
+        if failure.is_none() && !cut_short {
+            break;
+        }
+        // A body that ends before its announced size was cut off like a dropped connection.
+        let delay = match &failure {
+            Some(e) => client.retry_policy().delay(attempt, e, None),
+            None => {
+                let eof = io::Error::from(io::ErrorKind::UnexpectedEof).into();
+                client.retry_policy().delay(attempt, &eof, None)
+            }
+        };
+        match delay {
+            None => {
+                return Err(failure.unwrap_or(EDGARError::SizeMismatch {
+                    expected: remote_total.unwrap_or_default(),
+                    actual: offset,
+                }))
+            }
+            Some(d) => tokio::time::sleep(d).await,
+        }
+        attempt += 1;
+    }
+    writer.flush().await?;
+    let sha256 = format!("{:x}", hasher.finalize());
+    if let Some(expected) = download.expected_size {
+        if expected != offset {
+            return Err(EDGARError::SizeMismatch {
+                expected,
+                actual: offset,
+            });
+        }
+    }
+    if let Some(expected) = &download.expected_sha256 {
+        if *expected != sha256 {
+            return Err(EDGARError::ChecksumMismatch {
+                expected: expected.clone(),
+                actual: sha256,
+            });
+        }
+    }
+    Ok(Some(DownloadSummary {
+        bytes: offset,
+        sha256,
+        resumed_from,
+    }))
+}
+/// The size of the remote file: the expected size of `download`, or else the size announced in answer to a request for its first byte.
+async fn remote_size(client: &EdgarClient, download: &Download) -> Result<Option<u64>, EDGARError> {
+    if let Some(size) = download.expected_size {
+        return Ok(Some(size));
+    }
+    let mut headers = range_headers(0)?;
+    headers.insert(RANGE, HeaderValue::from_static("bytes=0-0"));
+    let res = client.get_streaming(download.url(), headers).await?;
+    Ok(response_total(&res))
+}
+/// The body is requested without compression, so that ranges count the bytes of the file itself.
+fn range_headers(offset: u64) -> Result<HeaderMap, EDGARError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+    if offset > 0 {
+        headers.insert(RANGE, HeaderValue::from_str(&format!("bytes={offset}-"))?);
+    }
+    Ok(headers)
+}
+/// The size of the whole file according to the headers of `res`.
+fn response_total(res: &EdgarStreamingResponse) -> Option<u64> {
+    let header = |name| res.headers.get(name)?.to_str().ok();
+    if res.status == StatusCode::PARTIAL_CONTENT {
+        // Such as `bytes 100-999/1000`, or `bytes 100-999/*` when the size is unknown.
+        return header(CONTENT_RANGE)?.rsplit('/').next()?.parse().ok();
+    }
+    if header(CONTENT_ENCODING).is_some_and(|e| e != "identity") {
+        return None;
+    }
+    header(CONTENT_LENGTH)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{
+        retry::RetryPolicy,
+        test_server::{client, TestResponse, TestServer},
+        transport::{EdgarRequest, Transport},
+        EdgarClientBuilder, EdgarResponse,
+    };
+    use async_trait::async_trait;
+    use futures::stream;
+    use std::{sync::Mutex, time::Duration};
+
+    const SUBMISSION: &[u8] =
+        b"<SEC-DOCUMENT>0000831001-23-000114.txt : 20230804\n</SEC-DOCUMENT>\n";
+
+    fn submission(server: &TestServer) -> Download {
+        let accession_number = "0000831001-23-000114".parse().unwrap();
+        Download::complete_submission(
+            &Endpoints::from_base(server.base()),
+            "831001",
+            &accession_number,
+        )
+        .unwrap()
+    }
+    fn sha256(data: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(data))
+    }
+    const PATH: &str = "/Archives/edgar/data/831001/000083100123000114/0000831001-23-000114.txt";
+
+    #[tokio::test]
+    async fn download_to_writer_with_progress() {
+        let server =
+            TestServer::start().route(PATH, vec![TestResponse::ok("text/plain", SUBMISSION)]);
+        let seen = Arc::new(Mutex::new(vec![]));
+        let progress = seen.clone();
+        let download = submission(&server)
+            .set_expected_size(SUBMISSION.len() as u64)
+            .set_expected_sha256(&sha256(SUBMISSION).to_uppercase())
+            .set_progress(move |p| progress.lock().unwrap().push(p));
+        let mut written = vec![];
+        let summary = super::download(client(&server), &download, &mut written)
+            .await
+            .unwrap();
+        assert_eq!(written, SUBMISSION);
+        assert_eq!(summary.bytes, SUBMISSION.len() as u64);
+        assert_eq!(summary.sha256, sha256(SUBMISSION));
+        let last = *seen.lock().unwrap().last().unwrap();
+        assert_eq!(last.downloaded, SUBMISSION.len() as u64);
+        assert_eq!(last.total, Some(SUBMISSION.len() as u64));
+        let request = &server.requests()[0];
+        assert!(request
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("accept-encoding: identity")))
+    }
+    #[tokio::test]
+    async fn download_verification_failures() {
+        let server =
+            TestServer::start().route(PATH, vec![TestResponse::ok("text/plain", SUBMISSION)]);
+        let too_short = submission(&server).set_expected_size(10);
+        let res = super::download(client(&server), &too_short, &mut vec![]).await;
+        assert!(matches!(
+            res,
+            Err(EDGARError::SizeMismatch { expected: 10, .. })
+        ));
+        let wrong_sha256 = submission(&server).set_expected_sha256(&sha256(b"other"));
+        let res = super::download(client(&server), &wrong_sha256, &mut vec![]).await;
+        assert!(matches!(res, Err(EDGARError::ChecksumMismatch { .. })))
+    }
+    /// The answer to a request for the first byte of [SUBMISSION].
+    fn first_byte() -> TestResponse {
+        TestResponse {
+            status: 206,
+            headers: vec![(
+                "Content-Range".to_string(),
+                format!("bytes 0-0/{}", SUBMISSION.len()),
+            )],
+            body: SUBMISSION[..1].to_vec(),
+        }
+    }
+    #[tokio::test]
+    async fn download_to_path_resumes() {
+        let len = SUBMISSION.len();
+        let server = TestServer::start().route(
+            PATH,
+            vec![TestResponse {
+                status: 206,
+                headers: vec![(
+                    "Content-Range".to_string(),
+                    format!("bytes 10-{}/{len}", len - 1),
+                )],
+                body: SUBMISSION[10..].to_vec(),
+            }],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0000831001-23-000114.txt");
+        std::fs::write(&path, &SUBMISSION[..10]).unwrap();
+        let download = submission(&server).set_expected_sha256(&sha256(SUBMISSION));
+        let summary = download_to_path(client(&server), &download, &path)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), SUBMISSION);
+        assert_eq!(summary.resumed_from, 10);
+        assert_eq!(summary.bytes, len as u64);
+        assert!(server.requests()[0]
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("range: bytes=10-")))
+    }
+    #[tokio::test]
+    async fn download_to_path_range_ignored_or_complete() {
+        let server = TestServer::start().route(
+            PATH,
+            vec![
+                TestResponse::ok("text/plain", SUBMISSION),
+                TestResponse::status(416),
+                first_byte(),
+            ],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0000831001-23-000114.txt");
+        std::fs::write(&path, &SUBMISSION[..10]).unwrap();
+        let download = submission(&server).set_expected_sha256(&sha256(SUBMISSION));
+        download_to_path(client(&server), &download, &path)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), SUBMISSION);
+        let summary = download_to_path(client(&server), &download, &path)
+            .await
+            .unwrap();
+        assert_eq!(summary.resumed_from, SUBMISSION.len() as u64);
+        assert_eq!(std::fs::read(&path).unwrap(), SUBMISSION);
+        assert_eq!(server.requests().len(), 3)
+    }
+    #[tokio::test]
+    async fn download_to_path_larger_than_remote() {
+        let server = TestServer::start().route(
+            PATH,
+            vec![
+                TestResponse::status(416),
+                first_byte(),
+                TestResponse::ok("text/plain", SUBMISSION),
+            ],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0000831001-23-000114.txt");
+        std::fs::write(&path, [SUBMISSION, b"stale"].concat()).unwrap();
+        let download = submission(&server).set_expected_sha256(&sha256(SUBMISSION));
+        let summary = download_to_path(client(&server), &download, &path)
+            .await
+            .unwrap();
+        assert_eq!(summary.resumed_from, 0);
+        assert_eq!(std::fs::read(&path).unwrap(), SUBMISSION);
+        assert!(server.requests()[1]
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("range: bytes=0-0")))
+    }
+
+    /// Drops the connection after the first 10 bytes of the first response.
+    #[derive(Debug, Default)]
+    struct DroppingTransport {
+        requests: Mutex<Vec<EdgarRequest>>,
+    }
+    #[async_trait]
+    impl Transport for DroppingTransport {
+        async fn send(&self, _: EdgarRequest) -> Result<EdgarResponse, EDGARError> {
+            unreachable!()
+        }
+        async fn send_streaming(
+            &self,
+            request: EdgarRequest,
+        ) -> Result<EdgarStreamingResponse, EDGARError> {
+            let range = request.headers.get(RANGE).cloned();
+            self.requests.lock().unwrap().push(request);
+            let mut headers = HeaderMap::new();
+            let len = SUBMISSION.len();
+            match range {
+                None => {
+                    headers.insert(CONTENT_LENGTH, HeaderValue::from(len));
+                    let chunks = vec![
+                        Ok(SUBMISSION[..10].to_vec()),
+                        Err(std::io::Error::from(std::io::ErrorKind::ConnectionReset).into()),
+                    ];
+                    Ok(EdgarStreamingResponse {
+                        status: StatusCode::OK,
+                        headers,
+                        body: stream::iter(chunks).boxed(),
+                    })
+                }
+                Some(_) => {
+                    let range = format!("bytes 10-{}/{len}", len - 1);
+                    headers.insert(CONTENT_RANGE, HeaderValue::from_str(&range).unwrap());
+                    Ok(EdgarStreamingResponse {
+                        status: StatusCode::PARTIAL_CONTENT,
+                        headers,
+                        body: stream::iter(vec![Ok(SUBMISSION[10..].to_vec())]).boxed(),
+                    })
+                }
+            }
+        }
+    }
+    #[tokio::test(start_paused = true)]
+    async fn download_resumes_dropped_connection() {
+        let transport = Arc::new(DroppingTransport::default());
+        let client = EdgarClientBuilder::new()
+            .set_user_agent("Sample Company Name admin@samplecompany.com")
+            .set_transport(transport.clone())
+            .set_retry_policy(RetryPolicy::new().set_base_delay(Duration::from_secs(2)))
+            .build()
+            .unwrap();
+        let start = tokio::time::Instant::now();
+        let accession_number = "0000831001-23-000114".parse().unwrap();
+        let download =
+            Download::complete_submission(client.endpoints(), "831001", &accession_number).unwrap();
+        let mut written = vec![];
+        let summary = super::download(client, &download, &mut written)
+            .await
+            .unwrap();
+        assert_eq!(written, SUBMISSION);
+        assert_eq!(summary.sha256, sha256(SUBMISSION));
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].headers.get(RANGE).unwrap(), "bytes=10-")
+    }
+}
@@ -0,0 +1,440 @@
+//! This module lists the documents of a filing, from its folder at `Archives/edgar/data/{cik}/{accession number without dashes}/`.
+//!
+//! The folder has two listings:
+//! - `index.json`, which lists every file of the folder, see [get_filing_directory]
+//! - `{accession number}-index.htm`, which describes the documents of the filing, see [get_filing_documents]
+//! ```
+//! use sec_edgar::{
+//!     archives::filing_index::get_filing_documents,
+//!     edgar::edgar_client,
+//!     edgar_query::filing_content_value::AccessionNumber,
+//! };
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let accession_number: AccessionNumber = "0000831001-23-000114".parse().unwrap();
+//!     let documents = get_filing_documents(client, "831001", &accession_number)
+//!         .await
+//!         .unwrap();
+//!     for document in documents {
+//!         println!("{:?} {} {}", document.document_type, document.file_name, document.url);
+//!     }
+//! }
+//! ```
+
+use crate::client::{endpoints::Endpoints, EdgarClient};
+use crate::edgar_query::filing_content_value::AccessionNumber;
+use crate::error::EDGARError;
+use crate::fields::date;
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::LazyLock;
+use url::Url;
+
+/// A file in the folder of a filing, as listed by `index.json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectoryItem {
+    /// The name of the file, such as `c-20230630.htm`.
+    pub name: String,
+    /// Whether the item is a folder rather than a file.
+    pub is_directory: bool,
+    /// The size in bytes. None for the listings EDGAR generates, such as the `-index.htm`.
+    pub size: Option<u64>,
+    /// The time at which the file was last modified.
+    pub last_modified: Option<NaiveDateTime>,
+    /// The URL of the file.
+    pub url: Url,
+}
+
+/// The folder of a filing, as listed by `index.json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilingDirectory {
+    /// The path of the folder, such as `/Archives/edgar/data/831001/000083100123000114`.
+    pub name: String,
+    /// The path of the folder of the filer, such as `/Archives/edgar/data/831001`.
+    pub parent_dir: String,
+    /// See [DirectoryItem]
+    pub items: Vec<DirectoryItem>,
+}
+
+#[derive(Deserialize)]
+struct RawIndex {
+    directory: RawDirectory,
+}
+#[derive(Deserialize)]
+struct RawDirectory {
+    name: String,
+    #[serde(rename = "parent-dir")]
+    parent_dir: String,
+    #[serde(default)]
+    item: Vec<RawItem>,
+}
+#[derive(Deserialize)]
+struct RawItem {
+    name: String,
+    #[serde(rename = "type")]
+    item_type: String,
+    #[serde(default)]
+    size: String,
+    #[serde(rename = "last-modified")]
+    last_modified: Option<String>,
+}
+
+/// A document of a filing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilingDocument {
+    /// The position of the document in the filing. None for the complete submission text file and files outside the document table.
+    pub sequence: Option<u32>,
+    /// The description of the document, such as `10-Q` or `GRAPHIC`.
+    pub description: Option<String>,
+    /// The type of the document, such as `10-Q` or `EX-31.1`.
+    pub document_type: Option<String>,
+    /// The name of the file, such as `c-20230630.htm`.
+    pub file_name: String,
+    /// The size in bytes.
+    pub size: Option<u64>,
+    /// The URL of the file.
+    pub url: Url,
+}
+
+static TABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<table[^>]*class="tableFile"[^>]*>(.*?)</table>"#).expect("valid regex")
+});
+static ROW_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<tr[^>]*>(.*?)</tr>").expect("valid regex"));
+static CELL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<td[^>]*>(.*?)</td>").expect("valid regex"));
+static LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<a[^>]*>(.*?)</a>").expect("valid regex"));
+static FILING_DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?is)<div class="infoHead">\s*Filing Date\s*</div>\s*<div class="info">([^<]*)</div>"#,
+    )
+    .expect("valid regex")
+});
+
+/// The path of the folder of a filing relative to the archives, without the leading zeros of `cik`.
+pub(crate) fn folder_path(cik: &str, accession_number: &AccessionNumber) -> String {
+    format!(
+        "Archives/edgar/data/{}/{}",
+        cik.trim_start_matches('0'),
+        accession_number.no_dashes()
+    )
+}
+fn file_url(endpoints: &Endpoints, folder: &str, file_name: &str) -> Result<Url, EDGARError> {
+    endpoints.www_url(&format!("{folder}/{file_name}"))
+}
+
+/// Get the listing of every file in the folder of the filing `accession_number` of the company with `cik`.
+pub async fn get_filing_directory(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+) -> Result<FilingDirectory, EDGARError> {
+    let folder = folder_path(cik, accession_number);
+    let url = client
+        .endpoints()
+        .www_url(&format!("{folder}/index.json"))?;
+    let raw: RawIndex = client.get_json(&url).await?;
+    let items = raw
+        .directory
+        .item
+        .into_iter()
+        .map(|item| {
+            Ok(DirectoryItem {
+                url: file_url(client.endpoints(), &folder, &item.name)?,
+                is_directory: item.item_type == "folder.gif",
+                size: item.size.trim().parse().ok(),
+                last_modified: item.last_modified.and_then(|m| {
+                    NaiveDateTime::parse_from_str(m.trim(), "%Y-%m-%d %H:%M:%S").ok()
+                }),
+                name: item.name,
+            })
+        })
+        .collect::<Result<_, EDGARError>>()?;
+    Ok(FilingDirectory {
+        name: raw.directory.name,
+        parent_dir: raw.directory.parent_dir,
+        items,
+    })
+}
+
+/// Get the documents of the filing `accession_number` of the company with `cik`.
+///
+/// The documents described by the `-index.htm` of the filing come first, in order.
+/// They are followed by the other files listed by `index.json`, such as the XBRL viewer pages, without sequence, description or type.
+pub async fn get_filing_documents(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+) -> Result<Vec<FilingDocument>, EDGARError> {
+    let folder = folder_path(cik, accession_number);
+    let url = client
+        .endpoints()
+        .www_url(&format!("{folder}/{}-index.htm", accession_number.value))?;
+    let index = client.get_text(&url).await?;
+    let mut documents = parse_document_tables(&index, client.endpoints(), &folder)?;
+    let directory = get_filing_directory(client, cik, accession_number).await?;
+    for item in directory.items {
+        if item.is_directory || documents.iter().any(|d| d.file_name == item.name) {
+            continue;
+        }
+        documents.push(FilingDocument {
+            sequence: None,
+            description: None,
+            document_type: None,
+            file_name: item.name,
+            size: item.size,
+            url: item.url,
+        });
+    }
+    Ok(documents)
+}
+
+/// The XML documents of a filing, as chosen by [get_xml_files].
+pub(crate) struct XmlFiles {
+    /// The date the filing was made, as shown by its `-index.htm`.
+    pub filing_date: Option<NaiveDate>,
+    /// The text of each chosen document, in the order of the document tables.
+    pub texts: Vec<String>,
+}
+
+/// Get the text of the XML documents of the filing `accession_number` of the company with `cik` for which `wanted` holds,
+/// such as the `primary_doc.xml` of a 13F. Only the `-index.htm` and the chosen documents are downloaded.
+///
+/// None if the filing has no `-index.htm`.
+pub(crate) async fn get_xml_files<F: Fn(&FilingDocument) -> bool>(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+    wanted: F,
+) -> Result<Option<XmlFiles>, EDGARError> {
+    let folder = folder_path(cik, accession_number);
+    let url = client
+        .endpoints()
+        .www_url(&format!("{folder}/{}-index.htm", accession_number.value))?;
+    let index = match client.get_text(&url).await {
+        Ok(index) => index,
+        Err(EDGARError::UnexpectedStatus { status: 404 }) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut texts = vec![];
+    for document in parse_document_tables(&index, client.endpoints(), &folder)? {
+        if document.file_name.to_ascii_lowercase().ends_with(".xml") && wanted(&document) {
+            texts.push(client.get_text(&document.url).await?);
+        }
+    }
+    Ok(Some(XmlFiles {
+        filing_date: filing_date(&index),
+        texts,
+    }))
+}
+
+/// Parses the rows of the `tableFile` tables of a `-index.htm`, which are the document format files and the data files.
+fn parse_document_tables(
+    index: &str,
+    endpoints: &Endpoints,
+    folder: &str,
+) -> Result<Vec<FilingDocument>, EDGARError> {
+    let mut documents = vec![];
+    for table in TABLE_REGEX.captures_iter(index) {
+        for row in ROW_REGEX.captures_iter(&table[1]) {
+            let cells: Vec<&str> = CELL_REGEX
+                .captures_iter(&row[1])
+                .filter_map(|c| c.get(1).map(|m| m.as_str()))
+                .collect();
+            // Header rows hold th cells only.
+            let [sequence, description, document, document_type, size] = cells[..] else {
+                continue;
+            };
+            let file_name = match LINK_REGEX.captures(document) {
+                Some(link) => html_text(&link[1]),
+                None => html_text(document),
+            };
+            let Some(file_name) = file_name else {
+                continue;
+            };
+            documents.push(FilingDocument {
+                sequence: html_text(sequence).and_then(|s| s.parse().ok()),
+                description: html_text(description),
+                document_type: html_text(document_type),
+                size: html_text(size).and_then(|s| s.parse().ok()),
+                url: file_url(endpoints, folder, &file_name)?,
+                file_name,
+            });
+        }
+    }
+    Ok(documents)
+}
+/// The `Filing Date` shown in the header of a `-index.htm`.
+fn filing_date(index: &str) -> Option<NaiveDate> {
+    date(&FILING_DATE_REGEX.captures(index)?[1]).ok()
+}
+/// The text of an HTML fragment without tags and with entities decoded. None if it is blank.
+fn html_text(html: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&#160;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, filing_index, TestResponse, TestServer};
+
+    fn server() -> TestServer {
+        TestServer::start()
+            .route(
+                "/Archives/edgar/data/831001/000083100123000114/index.json",
+                vec![TestResponse::ok(
+                    "application/json",
+                    include_bytes!("../../tests/fixtures/filing_index/index.json"),
+                )],
+            )
+            .route(
+                "/Archives/edgar/data/831001/000083100123000114/0000831001-23-000114-index.htm",
+                vec![TestResponse::ok(
+                    "text/html",
+                    include_bytes!(
+                        "../../tests/fixtures/filing_index/0000831001-23-000114-index.htm"
+                    ),
+                )],
+            )
+    }
+    fn accession_number() -> AccessionNumber {
+        "0000831001-23-000114".parse().unwrap()
+    }
+    #[test]
+    fn filing_index_html_text() {
+        assert_eq!(
+            html_text(" CEO &amp;\n CFO <span>iXBRL</span>"),
+            Some("CEO & CFO iXBRL".to_string())
+        );
+        assert_eq!(html_text("&nbsp;"), None)
+    }
+    #[test]
+    fn filing_index_filing_date() {
+        let index =
+            include_str!("../../tests/fixtures/filing_index/0000831001-23-000114-index.htm");
+        assert_eq!(filing_date(index), NaiveDate::from_ymd_opt(2023, 8, 4));
+        assert_eq!(filing_date("<html></html>"), None)
+    }
+    #[tokio::test]
+    async fn filing_index_get_filing_directory() {
+        let server = server();
+        let directory = get_filing_directory(client(&server), "0000831001", &accession_number())
+            .await
+            .unwrap();
+        assert_eq!(directory.parent_dir, "/Archives/edgar/data/831001");
+        assert_eq!(directory.items.len(), 9);
+        let submission = &directory.items[2];
+        assert_eq!(submission.name, "0000831001-23-000114.txt");
+        assert_eq!(submission.size, Some(47207815));
+        assert_eq!(
+            submission.last_modified.unwrap().to_string(),
+            "2023-08-04 16:31:50"
+        );
+        assert_eq!(directory.items[0].size, None);
+        assert!(submission
+            .url
+            .path()
+            .ends_with("/831001/000083100123000114/0000831001-23-000114.txt"))
+    }
+    #[tokio::test]
+    async fn filing_index_get_filing_documents() {
+        let server = server();
+        let documents = get_filing_documents(client(&server), "831001", &accession_number())
+            .await
+            .unwrap();
+        assert_eq!(
+            documents[0],
+            FilingDocument {
+                sequence: Some(1),
+                description: Some("10-Q".to_string()),
+                document_type: Some("10-Q".to_string()),
+                file_name: "c-20230630.htm".to_string(),
+                size: Some(16086546),
+                url: server
+                    .base()
+                    .join("Archives/edgar/data/831001/000083100123000114/c-20230630.htm")
+                    .unwrap(),
+            }
+        );
+        assert_eq!(
+            documents[1].description.as_deref(),
+            Some("CERTIFICATION OF CEO & CFO")
+        );
+        assert_eq!(documents[3].sequence, None);
+        assert_eq!(documents[3].document_type, None);
+        assert_eq!(documents[3].size, Some(47207815));
+        assert_eq!(documents[4].document_type.as_deref(), Some("EX-101.SCH"));
+        // The rest of the folder, without the documents already in the tables.
+        let rest: Vec<&str> = documents[5..]
+            .iter()
+            .map(|d| d.file_name.as_str())
+            .collect();
+        assert_eq!(
+            rest,
+            vec![
+                "0000831001-23-000114-index-headers.html",
+                "0000831001-23-000114-index.html",
+                "Financial_Report.xlsx",
+                "R1.htm",
+            ]
+        );
+        assert_eq!(documents[8].size, Some(7734))
+    }
+    #[tokio::test]
+    async fn filing_index_get_xml_files() {
+        let folder = "/Archives/edgar/data/1067983/000095012323008074";
+        let index = filing_index(
+            "2023-08-14",
+            &[
+                ("13F-HR", "primary_doc.html"),
+                ("13F-HR", "primary_doc.xml"),
+                ("INFORMATION TABLE", "46994.xml"),
+            ],
+        );
+        let server = TestServer::start()
+            .route(
+                &format!("{folder}/0000950123-23-008074-index.htm"),
+                vec![TestResponse::ok("text/html", &index)],
+            )
+            .route(
+                &format!("{folder}/primary_doc.xml"),
+                vec![TestResponse::ok("text/xml", b"<edgarSubmission/>")],
+            );
+        let thirteen_f = "0000950123-23-008074".parse().unwrap();
+        let files = get_xml_files(client(&server), "1067983", &thirteen_f, |d| {
+            d.file_name == "primary_doc.xml"
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(files.filing_date, NaiveDate::from_ymd_opt(2023, 8, 14));
+        assert_eq!(files.texts, vec!["<edgarSubmission/>"]);
+        // Neither the HTML rendering nor the unwanted XML document is downloaded.
+        assert_eq!(server.requests().len(), 2);
+        let missing = get_xml_files(client(&server), "831001", &accession_number(), |_| true)
+            .await
+            .unwrap();
+        assert!(missing.is_none())
+    }
+}
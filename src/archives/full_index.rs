@@ -0,0 +1,387 @@
+//! This module provides the quarterly indexes of every filing, at `Archives/edgar/full-index/{year}/QTR{quarter}/`.
+//!
+//! Each quarter has four indexes that list the same filings, sorted differently:
+//! - `form.idx` by form type, in fixed-width columns
+//! - `company.idx` by company name, in fixed-width columns
+//! - `master.idx` by CIK, separated by `|`
+//! - `crawler.idx` by company name, in fixed-width columns, with the URL of the filing index instead of the path of the filing
+//!
+//! Every index is parsed into the same [IndexEntry], one line at a time, as it is downloaded.
+//! ```
+//! use sec_edgar::{
+//!     archives::{full_index::{get_full_index, IndexFile}, Compression},
+//!     edgar::edgar_client,
+//! };
+//! use futures::StreamExt;
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let mut index = get_full_index(client, 2023, 3, IndexFile::Form, Compression::Gzip)
+//!         .await
+//!         .unwrap();
+//!     while let Some(entry) = index.next().await {
+//!         let entry = entry.unwrap();
+//!         if entry.form_type == "10-Q" {
+//!             println!("{} {}", entry.company_name, entry.path);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::archives::{
+    body_reader, decompress, read_latin1_line, read_latin1_line_async, Compression,
+};
+use crate::client::EdgarClient;
+use crate::edgar_query::{
+    edgar_query_builder::add_leading_zeros_to_cik, filing_content_value::AccessionNumber,
+};
+use crate::error::EDGARError;
+use crate::fields::invalid_field;
+use chrono::NaiveDate;
+use futures::stream::{self, BoxStream, StreamExt};
+use reqwest::header::HeaderMap;
+use std::io::BufRead;
+use tokio::io::AsyncBufRead;
+use url::Url;
+
+/// The index files of the archives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IndexFile {
+    /// Sorted by form type.
+    Form,
+    /// Sorted by company name.
+    Company,
+    /// Sorted by CIK.
+    Master,
+    /// Sorted by company name, linking to the filing index of each filing.
+    Crawler,
+}
+impl IndexFile {
+    /// The name of the file without extension, such as `form`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Form => "form",
+            Self::Company => "company",
+            Self::Master => "master",
+            Self::Crawler => "crawler",
+        }
+    }
+    /// The name of the column that follows the first one in fixed-width indexes.
+    fn second_column(&self) -> &'static str {
+        match self {
+            Self::Form => "Company Name",
+            _ => "Form Type",
+        }
+    }
+}
+
+/// A filing listed in an index file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexEntry {
+    /// The name of the company.
+    pub company_name: String,
+    /// The form type, such as `10-Q` or `SC 13G/A`.
+    pub form_type: String,
+    /// The CIK with leading zeros.
+    pub cik: String,
+    /// The date at which the filing was made.
+    pub date_filed: NaiveDate,
+    /// The path of the complete submission text file relative to `Archives/`, such as `edgar/data/831001/0000831001-23-000114.txt`.
+    /// For [IndexFile::Crawler], the URL of the filing index instead.
+    pub path: String,
+}
+impl IndexEntry {
+    /// The accession number of the filing, read from its path.
+    pub fn accession_number(&self) -> Option<AccessionNumber> {
+        let file_name = self.path.rsplit('/').next()?;
+        let stem = file_name
+            .strip_suffix("-index.htm")
+            .or_else(|| file_name.strip_suffix("-index.html"))
+            .or_else(|| file_name.strip_suffix(".txt"))?;
+        stem.parse().ok()
+    }
+}
+
+/// Parses an index file one line at a time.
+///
+/// The header of the file is skipped. Blank lines are ignored and malformed lines are returned as errors.
+/// An [IndexReader] of a [BufRead] is an [Iterator], and one of an [AsyncBufRead] is read with [IndexReader::next_entry].
+/// ```
+/// use sec_edgar::archives::full_index::{IndexFile, IndexReader};
+/// let master = "CIK|Company Name|Form Type|Date Filed|Filename\n\
+///     -----\n\
+///     831001|CITIGROUP INC|10-Q|2023-08-04|edgar/data/831001/0000831001-23-000114.txt\n";
+/// let mut entries = IndexReader::new(master.as_bytes(), IndexFile::Master);
+/// let entry = entries.next().unwrap().unwrap();
+/// assert_eq!(entry.cik, "0000831001");
+/// assert!(entries.next().is_none());
+/// ```
+#[derive(Debug)]
+pub struct IndexReader<R> {
+    reader: R,
+    file: IndexFile,
+    column_names: String,
+    second_column: Option<usize>,
+    in_entries: bool,
+}
+impl<R> IndexReader<R> {
+    /// Instantiates an [IndexReader] of `file` that reads from `reader`.
+    pub fn new(reader: R, file: IndexFile) -> Self {
+        Self {
+            reader,
+            file,
+            column_names: String::new(),
+            second_column: None,
+            in_entries: false,
+        }
+    }
+    /// Parses the next line of the file, which is an entry unless it belongs to the header or is blank.
+    fn parse_line(&mut self, line: String) -> Option<Result<IndexEntry, EDGARError>> {
+        if !self.in_entries {
+            // The header ends with a line of dashes under the column names.
+            if line.starts_with("---") {
+                self.second_column = self.column_names.find(self.file.second_column());
+                self.in_entries = true;
+            } else if !line.trim().is_empty() {
+                self.column_names = line;
+            }
+            return None;
+        }
+        if line.trim().is_empty() {
+            return None;
+        }
+        Some(self.parse(&line))
+    }
+    fn parse(&self, line: &str) -> Result<IndexEntry, EDGARError> {
+        let invalid = || invalid_field(&format!("{}.idx line", self.file.name()), line);
+        let (first, second, cik, date_filed, path) = match self.file {
+            IndexFile::Master => {
+                let mut fields = line.split('|');
+                let cik = fields.next().ok_or_else(invalid)?;
+                let name = fields.next().ok_or_else(invalid)?;
+                let form = fields.next().ok_or_else(invalid)?;
+                let date = fields.next().ok_or_else(invalid)?;
+                let path = fields.next().ok_or_else(invalid)?;
+                (name, form, cik, date, path)
+            }
+            _ => {
+                // The last three columns never hold whitespace, unlike names and form types.
+                let rest = line.trim_end();
+                let (rest, path) = rest.rsplit_once(char::is_whitespace).ok_or_else(invalid)?;
+                let (rest, date) = rest
+                    .trim_end()
+                    .rsplit_once(char::is_whitespace)
+                    .ok_or_else(invalid)?;
+                let (rest, cik) = rest
+                    .trim_end()
+                    .rsplit_once(char::is_whitespace)
+                    .ok_or_else(invalid)?;
+                let split = self.second_column.ok_or_else(invalid)?;
+                let split = rest
+                    .char_indices()
+                    .nth(split)
+                    .map_or(rest.len(), |(i, _)| i);
+                (&rest[..split], &rest[split..], cik, date, path)
+            }
+        };
+        let (company_name, form_type) = match self.file {
+            IndexFile::Form => (second, first),
+            _ => (first, second),
+        };
+        if cik.is_empty() || !cik.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        Ok(IndexEntry {
+            company_name: company_name.trim().to_string(),
+            form_type: form_type.trim().to_string(),
+            cik: add_leading_zeros_to_cik(cik),
+            date_filed: parse_index_date(date_filed.trim()).ok_or_else(invalid)?,
+            path: path.trim().to_string(),
+        })
+    }
+}
+impl<R: BufRead> Iterator for IndexReader<R> {
+    type Item = Result<IndexEntry, EDGARError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match read_latin1_line(&mut self.reader)? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(entry) = self.parse_line(line) {
+                return Some(entry);
+            }
+        }
+    }
+}
+impl<R: AsyncBufRead + Unpin> IndexReader<R> {
+    /// Reads the next entry as the file is downloaded. Returns None at the end of the file.
+    pub async fn next_entry(&mut self) -> Option<Result<IndexEntry, EDGARError>> {
+        loop {
+            let line = match read_latin1_line_async(&mut self.reader).await? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(entry) = self.parse_line(line) {
+                return Some(entry);
+            }
+        }
+    }
+}
+impl<R: AsyncBufRead + Unpin + Send + 'static> IndexReader<R> {
+    /// Turns the reader into a stream of its entries.
+    pub fn into_stream(self) -> BoxStream<'static, Result<IndexEntry, EDGARError>> {
+        stream::unfold(self, |mut reader| async move {
+            let entry = reader.next_entry().await?;
+            Some((entry, reader))
+        })
+        .boxed()
+    }
+}
+/// Index files write dates as `2023-08-04`, except for older daily indexes, which write them as `20230804`.
+pub(crate) fn parse_index_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y%m%d"))
+        .ok()
+}
+
+/// Get the `file` index of the `quarter` of `year`, downloaded in the `compression` format.
+/// The quarter must be from 1 to 4.
+///
+/// The entries are parsed as the index is downloaded, so neither the index nor its entries are held in memory as a whole.
+pub async fn get_full_index(
+    client: EdgarClient,
+    year: u16,
+    quarter: u8,
+    file: IndexFile,
+    compression: Compression,
+) -> Result<BoxStream<'static, Result<IndexEntry, EDGARError>>, EDGARError> {
+    if !(1..=4).contains(&quarter) {
+        return Err(invalid_field("quarter", &quarter.to_string()));
+    }
+    let url = client.endpoints().www_url(&format!(
+        "Archives/edgar/full-index/{year}/QTR{quarter}/{}.{}",
+        file.name(),
+        compression.extension()
+    ))?;
+    stream_index(client, &url, file).await
+}
+/// Requests the index file at `url` and streams its entries.
+pub(crate) async fn stream_index(
+    client: EdgarClient,
+    url: &Url,
+    file: IndexFile,
+) -> Result<BoxStream<'static, Result<IndexEntry, EDGARError>>, EDGARError> {
+    let res = client.get_streaming(url, HeaderMap::new()).await?;
+    let reader = decompress(body_reader(res.body)).await?;
+    Ok(IndexReader::new(reader, file).into_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archives::tests::{chunked, gzip, zip};
+    use crate::client::test_server::{client, TestResponse, TestServer};
+    use futures::TryStreamExt;
+
+    const FORM: &str = include_str!("../../tests/fixtures/full_index/form.idx");
+    const COMPANY: &str = include_str!("../../tests/fixtures/full_index/company.idx");
+    const MASTER: &str = include_str!("../../tests/fixtures/full_index/master.idx");
+    const CRAWLER: &str = include_str!("../../tests/fixtures/full_index/crawler.idx");
+
+    fn entries(index: &str, file: IndexFile) -> Vec<IndexEntry> {
+        IndexReader::new(index.as_bytes(), file)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+    fn apple() -> IndexEntry {
+        IndexEntry {
+            company_name: "APPLE INC".to_string(),
+            form_type: "SC 13G/A".to_string(),
+            cik: "0000320193".to_string(),
+            date_filed: NaiveDate::from_ymd_opt(2023, 7, 10).unwrap(),
+            path: "edgar/data/320193/0001104659-23-079633.txt".to_string(),
+        }
+    }
+    #[test]
+    fn full_index_every_format_agrees() {
+        let form = entries(FORM, IndexFile::Form);
+        let company = entries(COMPANY, IndexFile::Company);
+        let master = entries(MASTER, IndexFile::Master);
+        let crawler = entries(CRAWLER, IndexFile::Crawler);
+        assert_eq!(form.len(), 3);
+        assert_eq!(form[2], apple());
+        assert_eq!(company[0], apple());
+        assert_eq!(master[1], apple());
+        assert_eq!(crawler[0].form_type, "SC 13G/A");
+        assert_eq!(
+            crawler[0].path,
+            "https://www.sec.gov/Archives/edgar/data/320193/000110465923079633/0001104659-23-079633-index.htm"
+        );
+        for entries in [form, company, master, crawler] {
+            let accession = entries[0].accession_number().unwrap();
+            assert_eq!(accession.filer_id.len(), 10);
+        }
+    }
+    #[test]
+    fn full_index_malformed_line() {
+        let index = "CIK|Company Name|Form Type|Date Filed|Filename\n---\n831001|CITIGROUP INC\n\n";
+        let mut reader = IndexReader::new(index.as_bytes(), IndexFile::Master);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(EDGARError::InvalidFieldValue { .. }))
+        ));
+        assert!(reader.next().is_none())
+    }
+    #[tokio::test]
+    async fn full_index_read_as_downloaded() {
+        let reader = body_reader(chunked(MASTER.as_bytes()));
+        let mut entries = IndexReader::new(reader, IndexFile::Master);
+        let mut read = vec![];
+        while let Some(entry) = entries.next_entry().await {
+            read.push(entry.unwrap());
+        }
+        assert_eq!(read, self::entries(MASTER, IndexFile::Master));
+    }
+    #[test]
+    fn full_index_date_formats() {
+        let date = NaiveDate::from_ymd_opt(2023, 8, 4);
+        assert_eq!(parse_index_date("2023-08-04"), date);
+        assert_eq!(parse_index_date("20230804"), date);
+        assert_eq!(parse_index_date("08/04/2023"), None)
+    }
+    #[tokio::test]
+    async fn full_index_download_compressed() {
+        let server = TestServer::start()
+            .route(
+                "/Archives/edgar/full-index/2023/QTR3/form.gz",
+                vec![TestResponse::ok(
+                    "application/x-gzip",
+                    &gzip(FORM.as_bytes()),
+                )],
+            )
+            .route(
+                "/Archives/edgar/full-index/2023/QTR3/master.zip",
+                vec![TestResponse::ok(
+                    "application/zip",
+                    &zip("master.idx", MASTER.as_bytes()),
+                )],
+            );
+        let client = client(&server);
+        let form = get_full_index(client.clone(), 2023, 3, IndexFile::Form, Compression::Gzip)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let master = get_full_index(client.clone(), 2023, 3, IndexFile::Master, Compression::Zip)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(form.len(), 3);
+        assert_eq!(master[1], apple());
+        let invalid = get_full_index(client, 2023, 5, IndexFile::Form, Compression::Plain).await;
+        assert!(invalid.is_err())
+    }
+}
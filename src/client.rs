@@ -0,0 +1,665 @@
+//! This module houses the client that is used to send every request to EDGAR.
+//!
+//! Usage:
+//! ```
+//! use sec_edgar::client::EdgarClientBuilder;
+//! use std::time::Duration;
+//!
+//! let client = EdgarClientBuilder::new()
+//!     .set_user_agent("Sample Company Name admin@samplecompany.com")
+//!     .set_timeout(Duration::from_secs(30))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+pub mod cache;
+pub mod endpoints;
+pub mod rate_limit;
+pub mod retry;
+#[cfg(test)]
+pub(crate) mod test_server;
+pub mod transport;
+
+use crate::error::EDGARError;
+use cache::ResponseCache;
+use endpoints::Endpoints;
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use rate_limit::{RateLimiter, SEC_MAX_REQUESTS_PER_SECOND};
+use regex::Regex;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING, CONTENT_TYPE, USER_AGENT},
+    Client, Proxy, StatusCode,
+};
+use retry::{is_rate_threshold_exceeded_page, retry_after, RetryPolicy};
+use serde::de::DeserializeOwned;
+use std::{fmt, sync::Arc, time::Duration};
+use transport::{EdgarRequest, ReqwestTransport, Transport};
+use url::Url;
+
+/// The name of the environment variable that is read when no user agent is set on the [EdgarClientBuilder].
+pub const USER_AGENT_ENV_VAR: &str = "USER_AGENT";
+
+/// A reusable client that sends requests to EDGAR.
+///
+/// Cloning an [EdgarClient] is cheap and every clone shares the same [Transport] and [RateLimiter].
+#[derive(Clone, Debug)]
+pub struct EdgarClient {
+    transport: Arc<dyn Transport>,
+    headers: HeaderMap,
+    user_agent: String,
+    rate_limiter: RateLimiter,
+    retry_policy: RetryPolicy,
+    endpoints: Endpoints,
+    cache: Option<ResponseCache>,
+}
+impl EdgarClient {
+    /// Returns a new [EdgarClientBuilder].
+    pub fn builder() -> EdgarClientBuilder {
+        EdgarClientBuilder::new()
+    }
+    /// The user agent that is sent with every request.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+    /// The [RateLimiter] that throttles every request sent by this client.
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+    /// The [RetryPolicy] applied to every request sent by this client.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+    /// The [Endpoints] that requests built by this crate are sent to.
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+    /// The [ResponseCache] of this client, if any.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+    /// Sends a GET request to `url` once the [RateLimiter] allows it, retrying according to the [RetryPolicy].
+    ///
+    /// If the client has a [ResponseCache], a fresh cached response is returned without sending a request,
+    /// and a stale one is revalidated with EDGAR.
+    ///
+    /// Returns [EDGARError::RateThresholdExceeded] if EDGAR keeps answering with its rate threshold page,
+    /// and [EDGARError::UnexpectedStatus] if EDGAR keeps answering with an unsuccessful status.
+    pub async fn get(&self, url: &Url) -> Result<EdgarResponse, EDGARError> {
+        let cache = match &self.cache {
+            None => return self.fetch(url, HeaderMap::new()).await,
+            Some(c) => c,
+        };
+        let cached = match cache.load(url)? {
+            None => {
+                let res = self.fetch(url, HeaderMap::new()).await?;
+                cache.store(url, &res)?;
+                return Ok(res);
+            }
+            Some(c) => c,
+        };
+        if cache.is_fresh(url, &cached) {
+            return Ok(cached.response);
+        }
+        let res = self.fetch(url, cached.revalidation_headers()).await?;
+        if res.status == StatusCode::NOT_MODIFIED {
+            cache.store(url, &cached.response)?;
+            Ok(cached.response)
+        } else {
+            cache.store(url, &res)?;
+            Ok(res)
+        }
+    }
+    async fn fetch(&self, url: &Url, headers: HeaderMap) -> Result<EdgarResponse, EDGARError> {
+        let mut attempt = 1;
+        loop {
+            self.rate_limiter.acquire().await;
+            let (error, wait) = match self.send(url, headers.clone()).await {
+                Ok(res) => match res.check() {
+                    Ok(()) => return Ok(res),
+                    Err(e) => (e, retry_after(&res.headers)),
+                },
+                Err(e) => (e, None),
+            };
+            match self.retry_policy.delay(attempt, &error, wait) {
+                None => return Err(error),
+                Some(d) => tokio::time::sleep(d).await,
+            }
+            attempt += 1;
+        }
+    }
+    /// Sends a GET request to `url` and returns the body as text.
+    pub async fn get_text(&self, url: &Url) -> Result<String, EDGARError> {
+        Ok(self.get(url).await?.text())
+    }
+    /// Sends a GET request to `url` and deserializes the JSON body, as served by `data.sec.gov` and `efts.sec.gov`.
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &Url) -> Result<T, EDGARError> {
+        Ok(serde_json::from_slice(&self.get(url).await?.body)?)
+    }
+    /// Sends a GET request to `url` with the extra `headers` once the [RateLimiter] allows it,
+    /// and returns the response as soon as its headers have been read.
+    ///
+    /// The request is retried according to the [RetryPolicy] until EDGAR answers with a successful status,
+    /// but failures while the body is read are left to the caller. The [ResponseCache] is bypassed.
+    pub async fn get_streaming(
+        &self,
+        url: &Url,
+        headers: HeaderMap,
+    ) -> Result<EdgarStreamingResponse, EDGARError> {
+        let mut attempt = 1;
+        loop {
+            self.rate_limiter.acquire().await;
+            let (error, wait) = match self.send_streaming(url, headers.clone()).await {
+                Ok(res) if res.status.is_success() => return Ok(res),
+                Ok(res) => {
+                    let res = res.read().await?;
+                    let error = res.check().err().unwrap_or(EDGARError::UnexpectedStatus {
+                        status: res.status.as_u16(),
+                    });
+                    (error, retry_after(&res.headers))
+                }
+                Err(e) => (e, None),
+            };
+            match self.retry_policy.delay(attempt, &error, wait) {
+                None => return Err(error),
+                Some(d) => tokio::time::sleep(d).await,
+            }
+            attempt += 1;
+        }
+    }
+    fn request(&self, url: &Url, headers: HeaderMap) -> EdgarRequest {
+        let mut request = EdgarRequest {
+            url: url.clone(),
+            headers: self.headers.clone(),
+        };
+        request.headers.extend(headers);
+        request
+    }
+    async fn send(&self, url: &Url, headers: HeaderMap) -> Result<EdgarResponse, EDGARError> {
+        self.transport.send(self.request(url, headers)).await
+    }
+    async fn send_streaming(
+        &self,
+        url: &Url,
+        headers: HeaderMap,
+    ) -> Result<EdgarStreamingResponse, EDGARError> {
+        self.transport
+            .send_streaming(self.request(url, headers))
+            .await
+    }
+}
+
+/// A response from EDGAR whose body has been read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgarResponse {
+    /// The HTTP status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+impl EdgarResponse {
+    /// The body as text, decoded as ISO-8859-1 if the `Content-Type` says so and as UTF-8 otherwise.
+    pub fn text(&self) -> String {
+        let is_latin1 = self
+            .content_type()
+            .map(|c| {
+                let c = c.to_lowercase();
+                c.contains("iso-8859-1") || c.contains("latin1")
+            })
+            .unwrap_or(false);
+        if is_latin1 {
+            self.body.iter().map(|&b| b as char).collect()
+        } else {
+            String::from_utf8_lossy(&self.body).into_owned()
+        }
+    }
+    /// The `Content-Type` header, if any.
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers.get(CONTENT_TYPE)?.to_str().ok()
+    }
+    fn check(&self) -> Result<(), EDGARError> {
+        if is_rate_threshold_exceeded_page(self.content_type(), &self.body) {
+            Err(EDGARError::RateThresholdExceeded)
+        } else if !self.status.is_success() && self.status != StatusCode::NOT_MODIFIED {
+            Err(EDGARError::UnexpectedStatus {
+                status: self.status.as_u16(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A response from EDGAR whose body is read as it arrives, in chunks.
+pub struct EdgarStreamingResponse {
+    /// The HTTP status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The chunks of the body.
+    pub body: BoxStream<'static, Result<Vec<u8>, EDGARError>>,
+}
+impl EdgarStreamingResponse {
+    /// Reads the whole body.
+    pub async fn read(self) -> Result<EdgarResponse, EDGARError> {
+        let chunks: Vec<Vec<u8>> = self.body.try_collect().await?;
+        Ok(EdgarResponse {
+            status: self.status,
+            headers: self.headers,
+            body: chunks.concat(),
+        })
+    }
+}
+impl From<EdgarResponse> for EdgarStreamingResponse {
+    /// A response whose body is a single chunk.
+    fn from(res: EdgarResponse) -> Self {
+        Self {
+            status: res.status,
+            headers: res.headers,
+            body: futures::stream::once(async { Ok(res.body) }).boxed(),
+        }
+    }
+}
+impl fmt::Debug for EdgarStreamingResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EdgarStreamingResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builds an [EdgarClient] at runtime.
+///
+/// If no user agent is set, the `USER_AGENT` environment variable is read when [EdgarClientBuilder::build] is called.
+/// If the variable is not set at runtime either, the value of `USER_AGENT` at compile time is used.
+/// ```
+/// use sec_edgar::client::EdgarClientBuilder;
+/// let client = EdgarClientBuilder::new()
+///     .set_user_agent("Sample Company Name admin@samplecompany.com")
+///     .add_header("From", "admin@samplecompany.com")
+///     .build();
+/// ```
+#[derive(Debug, Default)]
+pub struct EdgarClientBuilder {
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    headers: Vec<(String, String)>,
+    requests_per_second: Option<u32>,
+    rate_limiter: Option<RateLimiter>,
+    retry_policy: Option<RetryPolicy>,
+    endpoints: Option<Endpoints>,
+    transport: Option<Arc<dyn Transport>>,
+    cache: Option<ResponseCache>,
+}
+impl EdgarClientBuilder {
+    /// Instantiates a builder with no user agent, no timeouts, no proxy and no extra headers.
+    /// Requests are limited to the SEC's maximum of 10 per second, retried according to [RetryPolicy::new] and sent to the SEC.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// [According to the SEC](https://www.sec.gov/os/webmaster-faq#developers), the user agent must be in the form:
+    /// ```txt
+    /// Sample Company Name AdminContact@<sample company domain>.com
+    /// ```
+    /// The user agent is validated when [EdgarClientBuilder::build] is called.
+    pub fn set_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+    /// The total time allowed for a request, from connecting until the body has been read.
+    /// Ignored if a [Transport] is set.
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// The time allowed to connect to EDGAR.
+    /// Ignored if a [Transport] is set.
+    pub fn set_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+    /// Sends every request through the proxy at `proxy_url`.
+    /// Ignored if a [Transport] is set.
+    pub fn set_proxy(mut self, proxy_url: &str) -> Self {
+        self.proxy = Some(proxy_url.to_string());
+        self
+    }
+    /// Adds a header that will be sent with every request.
+    pub fn add_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+    /// Limits the client to `requests_per_second` requests per second.
+    /// The SEC allows at most 10, which is the default.
+    pub fn set_rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.requests_per_second = Some(requests_per_second);
+        self
+    }
+    /// Shares `rate_limiter` with this client, so that several clients are throttled together.
+    /// Takes precedence over [EdgarClientBuilder::set_rate_limit].
+    pub fn set_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+    /// Sets how failed requests are retried. See [RetryPolicy].
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+    /// Sends requests to `endpoints` instead of the SEC. See [Endpoints].
+    pub fn set_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+    /// Sends requests with `transport` instead of a [ReqwestTransport] built from the timeouts and proxy of this builder.
+    pub fn set_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+    /// Caches responses on disk. See [ResponseCache].
+    pub fn set_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    /// Builds the [EdgarClient].
+    pub fn build(self) -> Result<EdgarClient, EDGARError> {
+        let user_agent = match self.user_agent {
+            Some(u) => validate_user_agent(&u)?,
+            None => validate_user_agent(&user_agent_from_env()?)?,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_str(&user_agent)?);
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        let transport = match self.transport {
+            Some(t) => t,
+            None => Arc::new(reqwest_transport(
+                self.timeout,
+                self.connect_timeout,
+                self.proxy,
+            )?),
+        };
+        let rate_limiter = match self.rate_limiter {
+            Some(r) => r,
+            None => RateLimiter::new(
+                self.requests_per_second
+                    .unwrap_or(SEC_MAX_REQUESTS_PER_SECOND),
+            )?,
+        };
+        Ok(EdgarClient {
+            transport,
+            headers,
+            user_agent,
+            rate_limiter,
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            endpoints: self.endpoints.unwrap_or_default(),
+            cache: self.cache,
+        })
+    }
+}
+
+fn reqwest_transport(
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+) -> Result<ReqwestTransport, EDGARError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
+    let mut builder = Client::builder()
+        .default_headers(headers)
+        .deflate(true)
+        .gzip(true);
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(Proxy::all(proxy.as_str())?);
+    }
+    Ok(ReqwestTransport::new(builder.build()?))
+}
+
+/// Validates that the user agent is a company name followed by a contact email, as required by the SEC.
+/// Surrounding whitespace is trimmed.
+/// ```
+/// use sec_edgar::client::validate_user_agent;
+/// assert!(validate_user_agent("Sample Company Name admin@samplecompany.com").is_ok());
+/// assert!(validate_user_agent("admin@samplecompany.com").is_err());
+/// ```
+pub fn validate_user_agent(user_agent: &str) -> Result<String, EDGARError> {
+    let user_agent = user_agent.trim();
+    let user_agent_regex = Regex::new(r"^\S.*\s+[^\s@]+@[^\s@]+\.[^\s@]+$")?;
+    if user_agent_regex.is_match(user_agent) {
+        Ok(user_agent.to_string())
+    } else {
+        Err(EDGARError::InvalidUserAgent)
+    }
+}
+fn user_agent_from_env() -> Result<String, EDGARError> {
+    match std::env::var(USER_AGENT_ENV_VAR) {
+        Ok(u) => Ok(u),
+        Err(_) => option_env!("USER_AGENT")
+            .map(|u| u.to_string())
+            .ok_or(EDGARError::UserAgentEnvVarMissing),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_server::{TestResponse, TestServer};
+
+    const SAMPLE_USER_AGENT: &str = "Sample Company Name admin@samplecompany.com";
+
+    #[test]
+    fn client_validate_user_agent() {
+        let res = validate_user_agent("  Sample Company Name admin@samplecompany.com ");
+        assert_eq!(res.unwrap(), SAMPLE_USER_AGENT)
+    }
+    #[test]
+    fn client_validate_user_agent_missing_email() {
+        assert!(validate_user_agent("Sample Company Name").is_err())
+    }
+    #[test]
+    fn client_validate_user_agent_missing_company() {
+        assert!(validate_user_agent("admin@samplecompany.com").is_err())
+    }
+    #[test]
+    fn client_builder_sets_user_agent() {
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_timeout(Duration::from_secs(5))
+            .add_header("From", "admin@samplecompany.com")
+            .build()
+            .unwrap();
+        assert_eq!(client.user_agent(), SAMPLE_USER_AGENT)
+    }
+    #[test]
+    fn client_builder_invalid_user_agent() {
+        let res = EdgarClientBuilder::new().set_user_agent("nope").build();
+        assert!(matches!(res, Err(EDGARError::InvalidUserAgent)))
+    }
+    #[test]
+    fn client_builder_invalid_header() {
+        let res = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .add_header("Bad Header", "value")
+            .build();
+        assert!(res.is_err())
+    }
+    #[test]
+    fn client_builder_default_rate_limit() {
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .build()
+            .unwrap();
+        assert_eq!(client.rate_limiter().requests_per_second(), 10)
+    }
+    #[test]
+    fn client_builder_invalid_rate_limit() {
+        let res = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_rate_limit(0)
+            .build();
+        assert!(matches!(res, Err(EDGARError::InvalidRateLimit)))
+    }
+    #[test]
+    fn client_response_text_latin1() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/atom+xml; charset=ISO-8859-1"),
+        );
+        let res = EdgarResponse {
+            status: StatusCode::OK,
+            headers,
+            body: vec![b'S', 0xE3, b'o'],
+        };
+        assert_eq!(res.text(), "São")
+    }
+    #[test]
+    fn client_response_check_status() {
+        let res = EdgarResponse {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            headers: HeaderMap::new(),
+            body: vec![],
+        };
+        assert!(matches!(
+            res.check(),
+            Err(EDGARError::UnexpectedStatus { status: 503 })
+        ))
+    }
+    #[test]
+    fn client_response_check_rate_threshold_page() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+        let res = EdgarResponse {
+            status: StatusCode::FORBIDDEN,
+            headers,
+            body: b"<h1>Request Rate Threshold Exceeded</h1>".to_vec(),
+        };
+        assert!(matches!(
+            res.check(),
+            Err(EDGARError::RateThresholdExceeded)
+        ))
+    }
+    #[tokio::test]
+    async fn client_get_retries_until_success() {
+        let server = TestServer::start().route(
+            "/flaky",
+            vec![
+                TestResponse::status(503).with_header("Retry-After", "0"),
+                TestResponse::ok("text/plain", b"ok"),
+            ],
+        );
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_endpoints(Endpoints::from_base(server.base()))
+            .build()
+            .unwrap();
+        let url = client.endpoints().www_url("flaky").unwrap();
+        assert_eq!(client.get_text(&url).await.unwrap(), "ok");
+        assert_eq!(server.requests().len(), 2)
+    }
+    #[tokio::test]
+    async fn client_get_gives_up_on_rate_threshold_page() {
+        let server = TestServer::start().route(
+            "/throttled",
+            vec![TestResponse::ok(
+                "text/html",
+                b"<title>SEC.gov | Request Rate Threshold Exceeded</title>",
+            )],
+        );
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_endpoints(Endpoints::from_base(server.base()))
+            .set_retry_policy(
+                RetryPolicy::new()
+                    .set_max_attempts(2)
+                    .set_base_delay(Duration::ZERO),
+            )
+            .build()
+            .unwrap();
+        let url = client.endpoints().www_url("throttled").unwrap();
+        let res = client.get(&url).await;
+        assert!(matches!(res, Err(EDGARError::RateThresholdExceeded)));
+        assert_eq!(server.requests().len(), 2)
+    }
+    #[tokio::test]
+    async fn client_sends_user_agent_to_endpoint() {
+        let server = TestServer::start().route("/", vec![TestResponse::ok("text/plain", b"")]);
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_endpoints(Endpoints::from_base(server.base()))
+            .build()
+            .unwrap();
+        client.get(client.endpoints().data()).await.unwrap();
+        let request = &server.requests()[0];
+        assert!(request.iter().any(
+            |h| h.to_lowercase() == format!("user-agent: {}", SAMPLE_USER_AGENT.to_lowercase())
+        ))
+    }
+    #[tokio::test]
+    async fn client_get_reuses_fresh_cached_response() {
+        let server = TestServer::start().route(
+            "/include/ticker.txt",
+            vec![TestResponse::ok("text/plain", b"c\t831001")],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_endpoints(Endpoints::from_base(server.base()))
+            .set_cache(ResponseCache::new(dir.path()))
+            .build()
+            .unwrap();
+        let url = client.endpoints().www_url("include/ticker.txt").unwrap();
+        for _ in 0..3 {
+            assert_eq!(client.get_text(&url).await.unwrap(), "c\t831001");
+        }
+        assert_eq!(server.requests().len(), 1)
+    }
+    #[tokio::test]
+    async fn client_get_revalidates_stale_cached_response() {
+        let server = TestServer::start().route(
+            "/include/ticker.txt",
+            vec![
+                TestResponse::ok("text/plain", b"c\t831001").with_header("ETag", "\"v1\""),
+                TestResponse::status(304),
+            ],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let client = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_endpoints(Endpoints::from_base(server.base()))
+            .set_cache(ResponseCache::new(dir.path()).set_default_ttl(Duration::ZERO))
+            .build()
+            .unwrap();
+        let url = client.endpoints().www_url("include/ticker.txt").unwrap();
+        client.get(&url).await.unwrap();
+        let revalidated = client.get_text(&url).await.unwrap();
+        assert_eq!(revalidated, "c\t831001");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]
+            .iter()
+            .any(|h| h.to_lowercase() == "if-none-match: \"v1\""))
+    }
+    #[test]
+    fn client_builder_invalid_proxy() {
+        let res = EdgarClientBuilder::new()
+            .set_user_agent(SAMPLE_USER_AGENT)
+            .set_proxy("not a proxy")
+            .build();
+        assert!(res.is_err())
+    }
+}
@@ -0,0 +1,178 @@
+//! This module provides an on-disk cache for the responses of an [EdgarClient](crate::client::EdgarClient).
+//!
+//! A cached response is reused until its time to live (TTL) runs out.
+//! Once it is stale, the client revalidates it with `If-None-Match` and `If-Modified-Since`,
+//! so that EDGAR only sends the body again if it has changed.
+//! ```
+//! use sec_edgar::client::{cache::ResponseCache, EdgarClientBuilder};
+//! use std::time::Duration;
+//!
+//! let cache = ResponseCache::new("./.edgar_cache")
+//!     .set_default_ttl(Duration::from_secs(60 * 60))
+//!     .set_ttl("/include/ticker.txt", Duration::from_secs(24 * 60 * 60));
+//! let client = EdgarClientBuilder::new()
+//!     .set_user_agent("Sample Company Name admin@samplecompany.com")
+//!     .set_cache(cache)
+//!     .build();
+//! ```
+
+use crate::client::{
+    transport::{read_recording, write_recording},
+    EdgarResponse,
+};
+use crate::error::EDGARError;
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use url::Url;
+
+/// The TTL of responses whose path matches none of the prefixes set with [ResponseCache::set_ttl].
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Caches successful responses in a directory, keyed by URL.
+///
+/// Responses are stored in the same format as [ReplayTransport](crate::client::transport::ReplayTransport) recordings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    default_ttl: Duration,
+    ttls: Vec<(String, Duration)>,
+}
+/// A response read from a [ResponseCache].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedResponse {
+    /// The cached response.
+    pub response: EdgarResponse,
+    /// When the response was stored or last revalidated.
+    pub stored_at: SystemTime,
+}
+impl ResponseCache {
+    /// Instantiates a [ResponseCache] in `dir` where responses live for [DEFAULT_TTL].
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            default_ttl: DEFAULT_TTL,
+            ttls: vec![],
+        }
+    }
+    /// The TTL of responses whose path matches none of the prefixes set with [ResponseCache::set_ttl].
+    pub fn set_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+    /// The TTL of responses whose URL path starts with `path_prefix`, such as `/cgi-bin/browse-edgar` or `/submissions/`.
+    /// If several prefixes match, the longest one wins.
+    pub fn set_ttl(mut self, path_prefix: &str, ttl: Duration) -> Self {
+        self.ttls.push((path_prefix.to_string(), ttl));
+        self
+    }
+    /// The directory holding the cached responses.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+    /// The TTL of the response to `url`.
+    pub fn ttl(&self, url: &Url) -> Duration {
+        self.ttls
+            .iter()
+            .filter(|(prefix, _)| url.path().starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+    /// Reads the cached response to `url`, fresh or not.
+    pub fn load(&self, url: &Url) -> Result<Option<CachedResponse>, EDGARError> {
+        Ok(
+            read_recording(&self.dir, url)?.map(|(response, stored_at)| CachedResponse {
+                response,
+                stored_at: UNIX_EPOCH + Duration::from_secs(stored_at.unwrap_or_default()),
+            }),
+        )
+    }
+    /// Caches `response` as the response to `url`, as of now.
+    pub fn store(&self, url: &Url, response: &EdgarResponse) -> Result<(), EDGARError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        write_recording(&self.dir, url, response, Some(now))
+    }
+    /// Whether `cached` is still within the TTL of `url`.
+    pub fn is_fresh(&self, url: &Url, cached: &CachedResponse) -> bool {
+        match cached.stored_at.elapsed() {
+            Ok(age) => age < self.ttl(url),
+            Err(_) => true,
+        }
+    }
+}
+impl CachedResponse {
+    /// The headers that ask EDGAR to answer `304 Not Modified` if the response has not changed.
+    pub fn revalidation_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = self.response.headers.get(ETAG) {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = self.response.headers.get(LAST_MODIFIED) {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::{header::HeaderValue, StatusCode};
+
+    fn response(headers: HeaderMap) -> EdgarResponse {
+        EdgarResponse {
+            status: StatusCode::OK,
+            headers,
+            body: b"c\t831001".to_vec(),
+        }
+    }
+    #[test]
+    fn cache_ttl_longest_prefix_wins() {
+        let cache = ResponseCache::new("unused")
+            .set_default_ttl(Duration::from_secs(1))
+            .set_ttl("/cgi-bin/", Duration::from_secs(2))
+            .set_ttl("/cgi-bin/browse-edgar", Duration::from_secs(3));
+        let feed = Url::parse("https://www.sec.gov/cgi-bin/browse-edgar?CIK=1").unwrap();
+        let ticker = Url::parse("https://www.sec.gov/include/ticker.txt").unwrap();
+        assert_eq!(cache.ttl(&feed), Duration::from_secs(3));
+        assert_eq!(cache.ttl(&ticker), Duration::from_secs(1))
+    }
+    #[test]
+    fn cache_store_then_load_is_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path());
+        let url = Url::parse("https://www.sec.gov/include/ticker.txt").unwrap();
+        assert!(cache.load(&url).unwrap().is_none());
+        cache.store(&url, &response(HeaderMap::new())).unwrap();
+        let cached = cache.load(&url).unwrap().unwrap();
+        assert_eq!(cached.response.body, b"c\t831001");
+        assert!(cache.is_fresh(&url, &cached));
+        let expired = cache.clone().set_default_ttl(Duration::ZERO);
+        assert!(!expired.is_fresh(&url, &cached))
+    }
+    #[test]
+    fn cache_revalidation_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        let cached = CachedResponse {
+            response: response(headers),
+            stored_at: SystemTime::now(),
+        };
+        let revalidation = cached.revalidation_headers();
+        assert_eq!(revalidation.get(IF_NONE_MATCH).unwrap(), "\"abc\"");
+        assert_eq!(
+            revalidation.get(IF_MODIFIED_SINCE).unwrap(),
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        )
+    }
+}
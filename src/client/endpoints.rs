@@ -0,0 +1,133 @@
+//! This module provides the base URLs of the services that make up EDGAR.
+//! Overriding them makes it possible to point the whole crate at a mirror or a local stand-in server.
+
+use crate::error::EDGARError;
+use url::Url;
+
+/// The default base URL of `www.sec.gov`, which serves browse-edgar, the ticker file and the archives.
+pub const SEC_WWW_URL: &str = "https://www.sec.gov/";
+/// The default base URL of `data.sec.gov`, which serves the submissions and XBRL APIs.
+pub const SEC_DATA_URL: &str = "https://data.sec.gov/";
+/// The default base URL of `efts.sec.gov`, which serves full-text search.
+pub const SEC_EFTS_URL: &str = "https://efts.sec.gov/";
+
+/// The base URLs used by an [EdgarClient](crate::client::EdgarClient) and the query builders.
+/// ```
+/// use sec_edgar::client::endpoints::Endpoints;
+/// use url::Url;
+///
+/// let sec = Endpoints::new();
+/// assert_eq!(sec.www().as_str(), "https://www.sec.gov/");
+///
+/// let local = Endpoints::from_base(&Url::parse("http://127.0.0.1:8080").unwrap());
+/// assert_eq!(local.data().as_str(), "http://127.0.0.1:8080/");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Endpoints {
+    www: Url,
+    data: Url,
+    efts: Url,
+}
+impl Endpoints {
+    /// Instantiates [Endpoints] that point at the SEC.
+    pub fn new() -> Self {
+        Self {
+            www: Url::parse(SEC_WWW_URL).expect("valid www.sec.gov URL"),
+            data: Url::parse(SEC_DATA_URL).expect("valid data.sec.gov URL"),
+            efts: Url::parse(SEC_EFTS_URL).expect("valid efts.sec.gov URL"),
+        }
+    }
+    /// Instantiates [Endpoints] where every service is served from `base`.
+    /// The paths of the services do not overlap, so a single stand-in server can answer all of them.
+    pub fn from_base(base: &Url) -> Self {
+        let base = with_trailing_slash(base.clone());
+        Self {
+            www: base.clone(),
+            data: base.clone(),
+            efts: base,
+        }
+    }
+    /// Replaces the base URL of `www.sec.gov`.
+    pub fn set_www(mut self, www: Url) -> Self {
+        self.www = with_trailing_slash(www);
+        self
+    }
+    /// Replaces the base URL of `data.sec.gov`.
+    pub fn set_data(mut self, data: Url) -> Self {
+        self.data = with_trailing_slash(data);
+        self
+    }
+    /// Replaces the base URL of `efts.sec.gov`.
+    pub fn set_efts(mut self, efts: Url) -> Self {
+        self.efts = with_trailing_slash(efts);
+        self
+    }
+    /// The base URL of `www.sec.gov`.
+    pub fn www(&self) -> &Url {
+        &self.www
+    }
+    /// The base URL of `data.sec.gov`.
+    pub fn data(&self) -> &Url {
+        &self.data
+    }
+    /// The base URL of `efts.sec.gov`.
+    pub fn efts(&self) -> &Url {
+        &self.efts
+    }
+    /// Joins `path` onto the base URL of `www.sec.gov`.
+    pub fn www_url(&self, path: &str) -> Result<Url, EDGARError> {
+        Ok(self.www.join(path.trim_start_matches('/'))?)
+    }
+    /// Joins `path` onto the base URL of `data.sec.gov`.
+    pub fn data_url(&self, path: &str) -> Result<Url, EDGARError> {
+        Ok(self.data.join(path.trim_start_matches('/'))?)
+    }
+    /// Joins `path` onto the base URL of `efts.sec.gov`.
+    pub fn efts_url(&self, path: &str) -> Result<Url, EDGARError> {
+        Ok(self.efts.join(path.trim_start_matches('/'))?)
+    }
+}
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Without a trailing slash, [Url::join] would replace the last segment of the base path.
+fn with_trailing_slash(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_default_points_at_sec() {
+        let endpoints = Endpoints::default();
+        assert_eq!(
+            endpoints.www_url("include/ticker.txt").unwrap().as_str(),
+            "https://www.sec.gov/include/ticker.txt"
+        );
+        assert_eq!(
+            endpoints
+                .data_url("/submissions/CIK0000831001.json")
+                .unwrap()
+                .as_str(),
+            "https://data.sec.gov/submissions/CIK0000831001.json"
+        );
+        assert_eq!(endpoints.efts().as_str(), SEC_EFTS_URL)
+    }
+    #[test]
+    fn endpoints_keep_base_path() {
+        let base = Url::parse("http://localhost:8080/mock").unwrap();
+        let endpoints = Endpoints::new().set_www(base);
+        assert_eq!(
+            endpoints.www_url("include/ticker.txt").unwrap().as_str(),
+            "http://localhost:8080/mock/include/ticker.txt"
+        )
+    }
+}
@@ -0,0 +1,129 @@
+//! This module provides the rate limiter that keeps requests within the SEC's fair access policy.
+//! [According to the SEC](https://www.sec.gov/os/webmaster-faq#developers), automated tools may send at most 10 requests per second.
+
+use crate::error::EDGARError;
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration, Instant};
+
+/// The maximum number of requests per second allowed by the SEC.
+pub const SEC_MAX_REQUESTS_PER_SECOND: u32 = 10;
+
+/// A token bucket that is shared by every clone of itself.
+///
+/// The bucket holds up to `requests_per_second` tokens and refills continuously.
+/// Each request takes one token, waiting for the bucket to refill if it is empty.
+/// ```
+/// use sec_edgar::client::rate_limit::RateLimiter;
+/// async fn some_func() {
+///     let limiter = RateLimiter::new(10).unwrap();
+///     limiter.acquire().await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    requests_per_second: u32,
+    bucket: Arc<Mutex<Bucket>>,
+}
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+impl RateLimiter {
+    /// Instantiates a [RateLimiter] that allows `requests_per_second` requests per second.
+    /// Returns an error if `requests_per_second` is zero.
+    pub fn new(requests_per_second: u32) -> Result<Self, EDGARError> {
+        if requests_per_second == 0 {
+            return Err(EDGARError::InvalidRateLimit);
+        }
+        Ok(Self {
+            requests_per_second,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: requests_per_second as f64,
+                last_refill: Instant::now(),
+            })),
+        })
+    }
+    /// The number of requests allowed per second.
+    pub fn requests_per_second(&self) -> u32 {
+        self.requests_per_second
+    }
+    /// Waits until a token is available and takes it.
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire() {
+            sleep(wait).await;
+        }
+    }
+    /// Takes a token if one is available, otherwise returns how long to wait until one will be.
+    fn try_acquire(&self) -> Option<Duration> {
+        let rate = self.requests_per_second as f64;
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(SEC_MAX_REQUESTS_PER_SECOND).expect("the SEC's rate limit is not zero")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_zero_is_invalid() {
+        assert!(matches!(
+            RateLimiter::new(0),
+            Err(EDGARError::InvalidRateLimit)
+        ))
+    }
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_allows_burst() {
+        let limiter = RateLimiter::new(10).unwrap();
+        let start = Instant::now();
+        for _ in 0..10 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(1))
+    }
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_throttles_after_burst() {
+        let limiter = RateLimiter::new(10).unwrap();
+        let start = Instant::now();
+        for _ in 0..30 {
+            limiter.acquire().await;
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(1990), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(2100), "{elapsed:?}")
+    }
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_is_shared_across_tasks() {
+        let limiter = RateLimiter::new(10).unwrap();
+        let start = Instant::now();
+        let tasks = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    for _ in 0..5 {
+                        limiter.acquire().await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(990))
+    }
+}
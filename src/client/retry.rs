@@ -0,0 +1,230 @@
+//! This module provides the policy used to retry requests that EDGAR failed to answer.
+//!
+//! Requests are retried when:
+//! - EDGAR answers with `429 Too Many Requests`, `500`, `502`, `503` or `504`.
+//! - EDGAR answers with its "Request Rate Threshold Exceeded" page.
+//! - The connection fails or times out.
+//! - The connection drops while the body is read.
+
+use crate::error::EDGARError;
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::{
+    io::ErrorKind,
+    time::{Duration, SystemTime},
+};
+
+/// The text found on the page the SEC serves when a client exceeds the fair access rate.
+pub const RATE_THRESHOLD_EXCEEDED_TEXT: &str = "Request Rate Threshold Exceeded";
+
+/// Decides how many times, and after how long, a failed request is retried.
+///
+/// The delay before attempt `n + 1` is drawn at random between half of and all of `base_delay * 2^(n - 1)`, capped at `max_delay`.
+/// If EDGAR sends a `Retry-After` header, its delay is used instead.
+/// A `Retry-After` longer than `max_delay` is honored by not retrying at all.
+/// ```
+/// use sec_edgar::client::retry::RetryPolicy;
+/// use std::time::Duration;
+/// let policy = RetryPolicy::new()
+///     .set_max_attempts(5)
+///     .set_base_delay(Duration::from_secs(1))
+///     .set_max_delay(Duration::from_secs(60));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+impl RetryPolicy {
+    /// Instantiates a [RetryPolicy] with the following defaults:
+    /// - `max_attempts`: 3
+    /// - `base_delay`: 500 milliseconds
+    /// - `max_delay`: 30 seconds
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+    /// A [RetryPolicy] that never retries.
+    pub fn none() -> Self {
+        Self::new().set_max_attempts(1)
+    }
+    /// The total number of attempts, including the first one. Zero is treated as one.
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+    /// The delay before the first retry, which doubles with every following retry.
+    pub fn set_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+    /// The longest delay between two attempts.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+    /// The total number of attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    /// Returns how long to wait before retrying after `attempt` failed with `error`, or `None` if the request should not be retried.
+    pub fn delay(
+        &self,
+        attempt: u32,
+        error: &EDGARError,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !is_retryable(error) {
+            return None;
+        }
+        match retry_after {
+            Some(r) if r > self.max_delay => None,
+            Some(r) => Some(r),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_delay);
+        if delay.is_zero() {
+            return delay;
+        }
+        rand::thread_rng().gen_range(delay / 2..=delay)
+    }
+}
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a request that failed with `error` may succeed if it is sent again.
+pub fn is_retryable(error: &EDGARError) -> bool {
+    match error {
+        EDGARError::RateThresholdExceeded => true,
+        EDGARError::UnexpectedStatus { status } => {
+            matches!(status, 429 | 500 | 502 | 503 | 504)
+        }
+        EDGARError::ReqwestError { source } => {
+            source.is_timeout() || source.is_connect() || source.is_body()
+        }
+        EDGARError::IOError { source } => matches!(
+            source.kind(),
+            ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::BrokenPipe
+                | ErrorKind::TimedOut
+                | ErrorKind::UnexpectedEof
+        ),
+        _ => false,
+    }
+}
+
+/// Parses the `Retry-After` header, which is either a number of seconds or an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Whether `body` is the page the SEC serves when a client exceeds the fair access rate.
+pub fn is_rate_threshold_exceeded_page(content_type: Option<&str>, body: &[u8]) -> bool {
+    let is_html = content_type
+        .map(|c| c.to_lowercase().starts_with("text/html"))
+        .unwrap_or(false);
+    is_html && String::from_utf8_lossy(body).contains(RATE_THRESHOLD_EXCEEDED_TEXT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn status(status: u16) -> EDGARError {
+        EDGARError::UnexpectedStatus { status }
+    }
+    #[test]
+    fn retry_retryable_statuses() {
+        assert!(is_retryable(&status(429)));
+        assert!(is_retryable(&status(503)));
+        assert!(is_retryable(&EDGARError::RateThresholdExceeded));
+        assert!(!is_retryable(&status(404)));
+        assert!(is_retryable(
+            &std::io::Error::from(ErrorKind::ConnectionReset).into()
+        ));
+        assert!(!is_retryable(
+            &std::io::Error::from(ErrorKind::NotFound).into()
+        ));
+        assert!(!is_retryable(&EDGARError::CIKNotFound));
+    }
+    #[test]
+    fn retry_stops_after_max_attempts() {
+        let policy = RetryPolicy::new().set_max_attempts(2);
+        assert!(policy.delay(1, &status(503), None).is_some());
+        assert!(policy.delay(2, &status(503), None).is_none());
+    }
+    #[test]
+    fn retry_none_never_retries() {
+        assert!(RetryPolicy::none().delay(1, &status(503), None).is_none())
+    }
+    #[test]
+    fn retry_backoff_is_jittered_and_capped() {
+        let policy = RetryPolicy::new()
+            .set_max_attempts(10)
+            .set_base_delay(Duration::from_secs(1))
+            .set_max_delay(Duration::from_secs(5));
+        let second = policy.delay(2, &status(503), None).unwrap();
+        assert!(second >= Duration::from_secs(1) && second <= Duration::from_secs(2));
+        let ninth = policy.delay(9, &status(503), None).unwrap();
+        assert!(ninth >= Duration::from_millis(2500) && ninth <= Duration::from_secs(5));
+    }
+    #[test]
+    fn retry_honors_retry_after() {
+        let policy = RetryPolicy::new().set_max_delay(Duration::from_secs(30));
+        let wait = Duration::from_secs(7);
+        assert_eq!(policy.delay(1, &status(429), Some(wait)), Some(wait));
+        let too_long = Duration::from_secs(600);
+        assert_eq!(policy.delay(1, &status(429), Some(too_long)), None);
+    }
+    #[test]
+    fn retry_parse_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)))
+    }
+    #[test]
+    fn retry_parse_retry_after_date_in_the_past() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO))
+    }
+    #[test]
+    fn retry_detects_rate_threshold_page() {
+        let body =
+            b"<html><head><title>SEC.gov | Request Rate Threshold Exceeded</title></head></html>";
+        assert!(is_rate_threshold_exceeded_page(
+            Some("text/html; charset=UTF-8"),
+            body
+        ));
+        assert!(!is_rate_threshold_exceeded_page(
+            Some("application/atom+xml"),
+            body
+        ));
+    }
+}
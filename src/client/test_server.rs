@@ -0,0 +1,176 @@
+//! A minimal HTTP server that stands in for EDGAR in tests.
+
+use crate::client::{endpoints::Endpoints, EdgarClient, EdgarClientBuilder};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use url::Url;
+
+/// A canned response.
+#[derive(Clone, Debug)]
+pub struct TestResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+impl TestResponse {
+    pub fn ok(content_type: &str, body: &[u8]) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.to_vec(),
+        }
+    }
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: vec![],
+        }
+    }
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Route {
+    path: String,
+    responses: VecDeque<TestResponse>,
+}
+
+/// Answers requests whose path and query start with a route's path.
+/// Each route answers with its responses in order, repeating the last one.
+#[derive(Clone, Debug)]
+pub struct TestServer {
+    base: Url,
+    routes: Arc<Mutex<Vec<Route>>>,
+    requests: Arc<Mutex<Vec<Vec<String>>>>,
+}
+impl TestServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let server = Self {
+            base,
+            routes: Default::default(),
+            requests: Default::default(),
+        };
+        let handler = server.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handler.handle(stream);
+            }
+        });
+        server
+    }
+    pub fn base(&self) -> &Url {
+        &self.base
+    }
+    pub fn route(self, path: &str, responses: Vec<TestResponse>) -> Self {
+        self.routes.lock().unwrap().push(Route {
+            path: path.to_string(),
+            responses: responses.into(),
+        });
+        self
+    }
+    /// The request line and headers of every request received so far.
+    pub fn requests(&self) -> Vec<Vec<String>> {
+        self.requests.lock().unwrap().clone()
+    }
+    fn handle(&self, mut stream: TcpStream) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut lines = vec![];
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                break;
+            }
+            lines.push(line.trim_end().to_string());
+        }
+        let target = lines
+            .first()
+            .and_then(|l| l.split_whitespace().nth(1))
+            .unwrap_or("/")
+            .to_string();
+        self.requests.lock().unwrap().push(lines);
+        let response = {
+            let mut routes = self.routes.lock().unwrap();
+            match routes.iter_mut().find(|r| target.starts_with(&r.path)) {
+                Some(route) if route.responses.len() > 1 => route.responses.pop_front().unwrap(),
+                Some(route) => route.responses[0].clone(),
+                None => TestResponse::status(404),
+            }
+        };
+        let mut head = format!(
+            "HTTP/1.1 {} Test\r\nContent-Length: {}\r\nConnection: close\r\n",
+            response.status,
+            response.body.len()
+        );
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+        let _ = stream.write_all(head.as_bytes());
+        let _ = stream.write_all(&response.body);
+    }
+}
+
+/// A client that sends its requests to `server`.
+pub(crate) fn client(server: &TestServer) -> EdgarClient {
+    EdgarClientBuilder::new()
+        .set_user_agent("Sample Company Name admin@samplecompany.com")
+        .set_endpoints(Endpoints::from_base(server.base()))
+        .build()
+        .unwrap()
+}
+
+/// A complete submission with a minimal header and each `(type, xml)` of `documents` wrapped in `<XML>`.
+pub(crate) fn xml_submission(
+    accession_number: &str,
+    submission_type: &str,
+    filed_as_of_date: &str,
+    documents: &[(&str, &str)],
+) -> Vec<u8> {
+    let mut submission = format!(
+        "<SEC-DOCUMENT>{accession_number}.txt : {filed_as_of_date}\n\
+         <SEC-HEADER>{accession_number}.hdr.sgml : {filed_as_of_date}\n\
+         ACCESSION NUMBER:\t\t{accession_number}\n\
+         CONFORMED SUBMISSION TYPE:\t{submission_type}\n\
+         PUBLIC DOCUMENT COUNT:\t\t{}\n\
+         FILED AS OF DATE:\t\t{filed_as_of_date}\n\
+         </SEC-HEADER>\n",
+        documents.len()
+    );
+    for (sequence, (document_type, xml)) in documents.iter().enumerate() {
+        submission += &format!(
+            "<DOCUMENT>\n<TYPE>{document_type}\n<SEQUENCE>{}\n<TEXT>\n<XML>\n{xml}\n</XML>\n</TEXT>\n</DOCUMENT>\n",
+            sequence + 1
+        );
+    }
+    submission += "</SEC-DOCUMENT>\n";
+    submission.into_bytes()
+}
+/// A `-index.htm` with a `Filing Date` such as `2023-08-14` and a document table row for each `(type, file name)` of `documents`.
+pub(crate) fn filing_index(filing_date: &str, documents: &[(&str, &str)]) -> Vec<u8> {
+    let mut index = format!(
+        "<html><body>\n\
+         <div class=\"formGrouping\">\n\
+         <div class=\"infoHead\">Filing Date</div>\n\
+         <div class=\"info\">{filing_date}</div>\n\
+         </div>\n\
+         <table class=\"tableFile\" summary=\"Document Format Files\">\n\
+         <tr><th>Seq</th><th>Description</th><th>Document</th><th>Type</th><th>Size</th></tr>\n"
+    );
+    for (sequence, (document_type, file_name)) in documents.iter().enumerate() {
+        index += &format!(
+            "<tr><td>{}</td><td>{document_type}</td><td><a href=\"{file_name}\">{file_name}</a></td><td>{document_type}</td><td>1000</td></tr>\n",
+            sequence + 1
+        );
+    }
+    index += "</table>\n</body></html>\n";
+    index.into_bytes()
+}
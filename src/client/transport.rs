@@ -0,0 +1,397 @@
+//! This module provides the transports that carry the requests of an [EdgarClient](crate::client::EdgarClient).
+//!
+//! By default, requests are sent over the network with [ReqwestTransport].
+//! [ReplayTransport] records responses to disk and replays them, which makes tests deterministic and offline.
+//! ```
+//! use sec_edgar::client::{transport::ReplayTransport, EdgarClientBuilder};
+//! let client = EdgarClientBuilder::new()
+//!     .set_user_agent("Sample Company Name admin@samplecompany.com")
+//!     .set_transport(ReplayTransport::replay("tests/fixtures/replay"))
+//!     .build();
+//! ```
+
+use crate::client::{EdgarResponse, EdgarStreamingResponse};
+use crate::error::EDGARError;
+use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING},
+    Client, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use url::Url;
+
+/// A GET request to EDGAR.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgarRequest {
+    /// The URL the request is sent to.
+    pub url: Url,
+    /// The headers sent with the request, including the user agent.
+    pub headers: HeaderMap,
+}
+
+/// Sends an [EdgarRequest] and returns the [EdgarResponse].
+///
+/// Rate limiting and retries are applied by the [EdgarClient](crate::client::EdgarClient) before a request reaches its transport,
+/// so implementations only need to deliver a single request.
+#[async_trait]
+pub trait Transport: Debug + Send + Sync {
+    /// Sends `request` and reads the whole response.
+    async fn send(&self, request: EdgarRequest) -> Result<EdgarResponse, EDGARError>;
+    /// Sends `request` and returns the response once its headers have been read, leaving the body to be read as it arrives.
+    ///
+    /// The default implementation reads the whole response with [Transport::send] first.
+    async fn send_streaming(
+        &self,
+        request: EdgarRequest,
+    ) -> Result<EdgarStreamingResponse, EDGARError> {
+        Ok(self.send(request).await?.into())
+    }
+}
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn send(&self, request: EdgarRequest) -> Result<EdgarResponse, EDGARError> {
+        (**self).send(request).await
+    }
+    async fn send_streaming(
+        &self,
+        request: EdgarRequest,
+    ) -> Result<EdgarStreamingResponse, EDGARError> {
+        (**self).send_streaming(request).await
+    }
+}
+
+/// Sends requests over the network with [reqwest].
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+impl ReqwestTransport {
+    /// Instantiates a [ReqwestTransport] that sends requests with `client`.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+impl Default for ReqwestTransport {
+    /// A [ReqwestTransport] that accepts gzip and deflate encoded responses.
+    fn default() -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
+        let client = Client::builder()
+            .default_headers(headers)
+            .deflate(true)
+            .gzip(true)
+            .build()
+            .unwrap_or_default();
+        Self::new(client)
+    }
+}
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: EdgarRequest) -> Result<EdgarResponse, EDGARError> {
+        let res = self
+            .client
+            .get(request.url.as_str())
+            .headers(request.headers)
+            .send()
+            .await?;
+        Ok(EdgarResponse {
+            status: res.status(),
+            headers: res.headers().clone(),
+            body: res.bytes().await?.to_vec(),
+        })
+    }
+    async fn send_streaming(
+        &self,
+        request: EdgarRequest,
+    ) -> Result<EdgarStreamingResponse, EDGARError> {
+        let res = self
+            .client
+            .get(request.url.as_str())
+            .headers(request.headers)
+            .send()
+            .await?;
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = stream::try_unfold(res, |mut res| async move {
+            Ok(res.chunk().await?.map(|chunk| (chunk.to_vec(), res)))
+        });
+        Ok(EdgarStreamingResponse {
+            status,
+            headers,
+            body: body.boxed(),
+        })
+    }
+}
+
+/// Whether a [ReplayTransport] answers from disk or records what another transport answers.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayMode {
+    /// Answer every request from disk. Requests without a recording fail with [EDGARError::FixtureNotFound].
+    Replay,
+    /// Send every request with the inner transport and save the response to disk.
+    Record,
+    /// Answer from disk when a recording exists, otherwise record it.
+    ReplayOrRecord,
+}
+
+/// Records responses to a directory, keyed by URL, and replays them.
+///
+/// Each response is stored as two files: `<key>.json`, holding the URL, status and headers, and `<key>.body`.
+/// The key is the [fixture_key] of the URL: its first 80 characters without the scheme, made safe for file names, then `_` and the hex SHA-256 of the whole URL.
+#[derive(Clone, Debug)]
+pub struct ReplayTransport {
+    dir: PathBuf,
+    mode: ReplayMode,
+    inner: Option<Arc<dyn Transport>>,
+}
+impl ReplayTransport {
+    /// Answers every request from the recordings in `dir`.
+    pub fn replay<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            mode: ReplayMode::Replay,
+            inner: None,
+        }
+    }
+    /// Sends every request with `inner` and records the responses in `dir`.
+    pub fn record<P: AsRef<Path>, T: Transport + 'static>(dir: P, inner: T) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            mode: ReplayMode::Record,
+            inner: Some(Arc::new(inner)),
+        }
+    }
+    /// Replays the recordings in `dir`, recording the responses of `inner` for requests that have none.
+    pub fn replay_or_record<P: AsRef<Path>, T: Transport + 'static>(dir: P, inner: T) -> Self {
+        Self {
+            mode: ReplayMode::ReplayOrRecord,
+            ..Self::record(dir, inner)
+        }
+    }
+    /// The directory holding the recordings.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+    /// The [ReplayMode] of this transport.
+    pub fn mode(&self) -> &ReplayMode {
+        &self.mode
+    }
+    fn load(&self, url: &Url) -> Result<Option<EdgarResponse>, EDGARError> {
+        Ok(read_recording(&self.dir, url)?.map(|(response, _)| response))
+    }
+    fn save(&self, url: &Url, response: &EdgarResponse) -> Result<(), EDGARError> {
+        write_recording(&self.dir, url, response, None)
+    }
+    async fn record_response(&self, request: EdgarRequest) -> Result<EdgarResponse, EDGARError> {
+        let inner = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| EDGARError::FixtureNotFound {
+                url: request.url.to_string(),
+            })?;
+        let url = request.url.clone();
+        let response = inner.send(request).await?;
+        self.save(&url, &response)?;
+        Ok(response)
+    }
+}
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&self, request: EdgarRequest) -> Result<EdgarResponse, EDGARError> {
+        match self.mode {
+            ReplayMode::Record => self.record_response(request).await,
+            ReplayMode::Replay => {
+                self.load(&request.url)?
+                    .ok_or_else(|| EDGARError::FixtureNotFound {
+                        url: request.url.to_string(),
+                    })
+            }
+            ReplayMode::ReplayOrRecord => match self.load(&request.url)? {
+                Some(response) => Ok(response),
+                None => self.record_response(request).await,
+            },
+        }
+    }
+}
+#[derive(Debug, Deserialize, Serialize)]
+struct Recording {
+    url: String,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    /// Seconds since the Unix epoch at which the response was stored, used by the response cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stored_at: Option<u64>,
+}
+
+/// Reads the response to `url` stored in `dir`, along with the time it was stored at, if it was given one.
+pub(crate) fn read_recording(
+    dir: &Path,
+    url: &Url,
+) -> Result<Option<(EdgarResponse, Option<u64>)>, EDGARError> {
+    let key = fixture_key(url);
+    let meta_path = dir.join(format!("{key}.json"));
+    if !meta_path.is_file() {
+        return Ok(None);
+    }
+    let meta: Recording = serde_json::from_slice(&fs::read(meta_path)?)?;
+    let mut headers = HeaderMap::new();
+    for (name, value) in meta.headers {
+        headers.insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(&value)?,
+        );
+    }
+    let response = EdgarResponse {
+        status: StatusCode::from_u16(meta.status).map_err(|_| EDGARError::UnexpectedStatus {
+            status: meta.status,
+        })?,
+        headers,
+        body: fs::read(dir.join(format!("{key}.body")))?,
+    };
+    Ok(Some((response, meta.stored_at)))
+}
+/// Stores the response to `url` in `dir`.
+pub(crate) fn write_recording(
+    dir: &Path,
+    url: &Url,
+    response: &EdgarResponse,
+    stored_at: Option<u64>,
+) -> Result<(), EDGARError> {
+    let key = fixture_key(url);
+    let meta = Recording {
+        url: url.to_string(),
+        status: response.status.as_u16(),
+        headers: response
+            .headers
+            .iter()
+            .filter_map(|(n, v)| Some((n.to_string(), v.to_str().ok()?.to_string())))
+            .collect(),
+        stored_at,
+    };
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(format!("{key}.json")),
+        serde_json::to_vec_pretty(&meta)?,
+    )?;
+    fs::write(dir.join(format!("{key}.body")), &response.body)?;
+    Ok(())
+}
+
+/// The longest readable prefix of a fixture key, which keeps file names under the limits of file systems.
+const FIXTURE_PREFIX_LEN: usize = 80;
+
+/// The name, without extension, of the files a [ReplayTransport] stores the response to `url` in.
+///
+/// The key starts with the URL, without scheme, with every character other than letters, digits, `.` and `-` replaced by `_`,
+/// truncated to 80 characters. It ends with the SHA-256 of the whole URL, which tells apart URLs that read alike.
+/// ```
+/// use sec_edgar::client::transport::fixture_key;
+/// use url::Url;
+/// let url = Url::parse("https://www.sec.gov/include/ticker.txt").unwrap();
+/// assert!(fixture_key(&url).starts_with("www.sec.gov_include_ticker.txt_"));
+/// ```
+pub fn fixture_key(url: &Url) -> String {
+    let without_scheme = &url.as_str()[url.scheme().len() + 3..];
+    let prefix: String = without_scheme
+        .chars()
+        .take(FIXTURE_PREFIX_LEN)
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' => c,
+            _ => '_',
+        })
+        .collect();
+    let digest = Sha256::digest(url.as_str().as_bytes());
+    format!("{prefix}_{:x}", digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{TestResponse, TestServer};
+
+    fn request(url: &Url) -> EdgarRequest {
+        EdgarRequest {
+            url: url.clone(),
+            headers: HeaderMap::new(),
+        }
+    }
+    #[test]
+    fn transport_fixture_key() {
+        let url =
+            Url::parse("https://www.sec.gov/cgi-bin/browse-edgar?action=getcompany&CIK=0000831001")
+                .unwrap();
+        let key = fixture_key(&url);
+        assert!(
+            key.starts_with("www.sec.gov_cgi-bin_browse-edgar_action_getcompany_CIK_0000831001_")
+        );
+        assert_eq!(key.len(), 65 + 1 + 64);
+    }
+    #[test]
+    fn transport_fixture_key_collisions() {
+        let urls = [
+            "https://www.sec.gov/a/b?x=1",
+            "https://www.sec.gov/a_b_x_1",
+            "https://www.sec.gov/a/b?x&1",
+        ];
+        let keys: Vec<String> = urls
+            .iter()
+            .map(|url| fixture_key(&Url::parse(url).unwrap()))
+            .collect();
+        assert_ne!(keys[0], keys[1]);
+        assert_ne!(keys[0], keys[2]);
+        assert_ne!(keys[1], keys[2]);
+        let long = Url::parse(&format!(
+            "https://efts.sec.gov/LATEST/search-index?q={}",
+            "a".repeat(500)
+        ))
+        .unwrap();
+        assert!(fixture_key(&long).len() <= FIXTURE_PREFIX_LEN + 1 + 64);
+    }
+    #[tokio::test]
+    async fn transport_replay_missing_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = Url::parse("https://www.sec.gov/include/ticker.txt").unwrap();
+        let res = ReplayTransport::replay(dir.path())
+            .send(request(&url))
+            .await;
+        assert!(matches!(res, Err(EDGARError::FixtureNotFound { .. })))
+    }
+    #[tokio::test]
+    async fn transport_record_then_replay() {
+        let server = TestServer::start().route(
+            "/include/ticker.txt",
+            vec![TestResponse::ok("text/plain", b"c\t831001")],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let url = server.base().join("include/ticker.txt").unwrap();
+        let recorder = ReplayTransport::record(dir.path(), ReqwestTransport::default());
+        let recorded = recorder.send(request(&url)).await.unwrap();
+        let replayed = ReplayTransport::replay(dir.path())
+            .send(request(&url))
+            .await
+            .unwrap();
+        assert_eq!(replayed.body, b"c\t831001");
+        assert_eq!(replayed.status, recorded.status);
+        assert_eq!(replayed.content_type(), Some("text/plain"));
+        assert_eq!(server.requests().len(), 1)
+    }
+    #[tokio::test]
+    async fn transport_replay_or_record_only_records_once() {
+        let server = TestServer::start().route("/", vec![TestResponse::ok("text/plain", b"ok")]);
+        let dir = tempfile::tempdir().unwrap();
+        let transport = ReplayTransport::replay_or_record(dir.path(), ReqwestTransport::default());
+        for _ in 0..3 {
+            transport.send(request(server.base())).await.unwrap();
+        }
+        assert_eq!(server.requests().len(), 1)
+    }
+}
@@ -33,17 +33,20 @@
 //! }
 //! ```
 
-use crate::edgar_query::filing_content_value::FilingContentValue;
+use crate::client::{EdgarClient, EdgarClientBuilder};
+use crate::edgar_query::{
+    company_info::CompanyInfo, edgar_query_builder::EdgarQueryBuilder,
+    filing_content_value::FilingContentValue,
+};
 use crate::error::EDGARError;
 use atom_syndication::{Entry, Feed};
-use reqwest::{
-    header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, HOST, USER_AGENT},
-    Client,
-};
+use chrono::NaiveDate;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use std::collections::VecDeque;
 use url::Url;
 
-/// There is additional information in the atom formatted feed that can be extracted if desired.
-/// An example of such info prior to an entry is shown below.
+/// There is additional information in the atom formatted feed, prior to the entries, that is not part of the [Feed].
+/// It can be extracted with [get_company_info].
 ///
 /// From `https://www.sec.gov/cgi-bin/browse-edgar?action=getcompany&CIK=0000002488&type=10-K&count=10&output=atom`
 /// ```xml
@@ -74,9 +77,8 @@ use url::Url;
 /// let client = edgar_client().unwrap();
 /// let feed = get_feed(client, some_url);
 /// ```
-pub async fn get_feed(client: Client, query_url: Url) -> Result<Feed, EDGARError> {
-    let res = client.get(query_url.as_str()).send().await?;
-    Ok(res.text().await?.parse::<Feed>()?)
+pub async fn get_feed(client: EdgarClient, query_url: Url) -> Result<Feed, EDGARError> {
+    Ok(client.get_text(&query_url).await?.parse::<Feed>()?)
     // match client.get(query_url.as_str()).send().await {
     //     Err(_) => Err(EDGARError::GettingFeedFailed),
     //     Ok(f) => match f.text().await {
@@ -86,6 +88,26 @@ pub async fn get_feed(client: Client, query_url: Url) -> Result<Feed, EDGARError
     // }
 }
 
+/// Get the `<company-info>` block of the feed, which holds the company's names, addresses, SIC and fiscal year end.
+///
+/// Usage:
+/// ```
+/// use sec_edgar::edgar::{edgar_client, get_company_info};
+/// use url::Url;
+/// async fn some_func() {
+///     let some_url = Url::parse("https://www.sec.gov/cgi-bin/browse-edgar?action=getcompany&CIK=0000002488&type=10-K&count=10&output=atom").unwrap();
+///     let client = edgar_client().unwrap();
+///     let company_info = get_company_info(client, some_url).await.unwrap();
+///     println!("{}", company_info.conformed_name);
+/// }
+/// ```
+pub async fn get_company_info(
+    client: EdgarClient,
+    query_url: Url,
+) -> Result<CompanyInfo, EDGARError> {
+    CompanyInfo::new(&client.get_text(&query_url).await?)
+}
+
 /// Get the feed entries, which will be in atom format.
 /// A brief example of such an entry can be found below.
 ///
@@ -113,12 +135,189 @@ pub async fn get_feed(client: Client, query_url: Url) -> Result<Feed, EDGARError
 ///     let feed_entries = get_feed_entries(client, some_url).await.unwrap();
 /// }
 /// ```
-pub async fn get_feed_entries(client: Client, query_url: Url) -> Result<Vec<Entry>, EDGARError> {
+pub async fn get_feed_entries(
+    client: EdgarClient,
+    query_url: Url,
+) -> Result<Vec<Entry>, EDGARError> {
     let entries = get_feed(client, query_url).await?.entries;
     Ok(entries)
 }
-/// Get the content of a feed entry.
-/// Because the serde-xml-rs crate fails at parsing XML values with an `=` symbol, URL links have been removed.
+/// Get every entry of the feed of `query`, walking successive pages with the `start` parameter.
+///
+/// Each page holds `count` entries, so setting the count of `query` to "100" takes the fewest requests.
+/// Paging stops once a page comes back empty or short.
+/// If `filed_since` is given, paging also stops at the first entry filed before it, and only entries filed on or after it are returned.
+///
+/// To process the entries as they arrive rather than all at once, see [stream_feed_entries].
+///
+/// Usage:
+/// ```
+/// use sec_edgar::{
+///     edgar::{edgar_client, get_all_feed_entries},
+///     edgar_query::edgar_query_builder::EdgarQueryBuilder,
+/// };
+/// use chrono::NaiveDate;
+/// async fn some_func() {
+///     let query = EdgarQueryBuilder::new("831001").set_count("100");
+///     let client = edgar_client().unwrap();
+///     let since = NaiveDate::from_ymd_opt(2020, 1, 1);
+///     let entries = get_all_feed_entries(client, query, since).await.unwrap();
+/// }
+/// ```
+pub async fn get_all_feed_entries(
+    client: EdgarClient,
+    query: EdgarQueryBuilder,
+    filed_since: Option<NaiveDate>,
+) -> Result<Vec<Entry>, EDGARError> {
+    stream_feed_entries(client, query, filed_since)
+        .try_collect()
+        .await
+}
+/// Streams every entry of the feed of `query`, fetching the next page only once the entries of the previous one have been consumed.
+///
+/// Pages are walked as in [get_all_feed_entries]. The stream ends after the first error.
+/// Dropping the stream, or ending it early with combinators such as [take](StreamExt::take), stops the requests.
+///
+/// Usage:
+/// ```
+/// use sec_edgar::{
+///     edgar::{edgar_client, stream_feed_entries},
+///     edgar_query::edgar_query_builder::EdgarQueryBuilder,
+/// };
+/// use futures::{StreamExt, TryStreamExt};
+/// async fn some_func() {
+///     let query = EdgarQueryBuilder::new("831001").set_count("100");
+///     let client = edgar_client().unwrap();
+///     let latest_250 = stream_feed_entries(client, query, None)
+///         .take(250)
+///         .try_collect::<Vec<_>>()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub fn stream_feed_entries(
+    client: EdgarClient,
+    query: EdgarQueryBuilder,
+    filed_since: Option<NaiveDate>,
+) -> BoxStream<'static, Result<Entry, EDGARError>> {
+    let pages = FeedPages {
+        client,
+        query,
+        filed_since,
+        fetched: 0,
+        page_size: None,
+        buffer: VecDeque::new(),
+        done: false,
+    };
+    stream::unfold(pages, |mut pages| async move {
+        let item = pages.next_entry().await?;
+        Some((item, pages))
+    })
+    .boxed()
+}
+/// Streams every entry of the feed of `query` along with its parsed content.
+/// See [stream_feed_entries] for how pages are fetched.
+///
+/// Usage:
+/// ```
+/// use sec_edgar::{
+///     edgar::{edgar_client, stream_filings},
+///     edgar_query::edgar_query_builder::EdgarQueryBuilder,
+/// };
+/// use futures::StreamExt;
+/// async fn some_func() {
+///     let query = EdgarQueryBuilder::new("831001").set_count("100");
+///     let client = edgar_client().unwrap();
+///     let mut filings = stream_filings(client, query, None);
+///     while let Some(filing) = filings.next().await {
+///         println!("{}", filing.unwrap().content.filing_href.value);
+///     }
+/// }
+/// ```
+pub fn stream_filings(
+    client: EdgarClient,
+    query: EdgarQueryBuilder,
+    filed_since: Option<NaiveDate>,
+) -> BoxStream<'static, Result<FeedFiling, EDGARError>> {
+    stream_feed_entries(client, query, filed_since)
+        .map(|entry| entry.and_then(FeedFiling::new))
+        .boxed()
+}
+/// A feed entry along with its parsed content.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeedFiling {
+    /// The entry, holding the metadata of the filing such as its title, link and update time.
+    pub entry: Entry,
+    /// See [FilingContentValue]
+    pub content: FilingContentValue,
+}
+impl FeedFiling {
+    /// Parses the content of `entry`.
+    pub fn new(entry: Entry) -> Result<Self, EDGARError> {
+        let content = get_feed_entry_content(&entry)?;
+        Ok(Self { entry, content })
+    }
+}
+/// The state of [stream_feed_entries] between two entries.
+struct FeedPages {
+    client: EdgarClient,
+    query: EdgarQueryBuilder,
+    filed_since: Option<NaiveDate>,
+    fetched: usize,
+    page_size: Option<usize>,
+    buffer: VecDeque<Entry>,
+    done: bool,
+}
+impl FeedPages {
+    async fn next_entry(&mut self) -> Option<Result<Entry, EDGARError>> {
+        loop {
+            if let Some(entry) = self.buffer.pop_front() {
+                if self
+                    .filed_since
+                    .is_some_and(|since| entry_filing_date(&entry) < since)
+                {
+                    self.done = true;
+                    self.buffer.clear();
+                    return None;
+                }
+                return Some(Ok(entry));
+            }
+            if self.done {
+                return None;
+            }
+            match self.next_page().await {
+                Ok(page) => self.buffer.extend(page),
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+    async fn next_page(&mut self) -> Result<Vec<Entry>, EDGARError> {
+        let page_query = self
+            .query
+            .clone()
+            .set_start(&self.fetched.to_string())
+            .build()?;
+        let page = get_feed_entries(self.client.clone(), page_query).await?;
+        self.fetched += page.len();
+        // EDGAR rounds the count down, so the first page tells how many entries a full page holds.
+        let page_size = *self.page_size.get_or_insert(page.len());
+        if page.is_empty() || page.len() < page_size {
+            self.done = true;
+        }
+        Ok(page)
+    }
+}
+/// The filing date of the content of `entry`, falling back to the date the entry was updated.
+pub(crate) fn entry_filing_date(entry: &Entry) -> NaiveDate {
+    match get_feed_entry_content(entry) {
+        Ok(content) => content.filing_date.date,
+        Err(_) => entry.updated().date_naive(),
+    }
+}
+/// Get the content of a feed entry, including the links to the filing index, the file number and the XBRL viewer.
 ///
 /// Usage:
 /// ```
@@ -140,50 +339,45 @@ pub fn get_feed_entry_content(entry: &Entry) -> Result<FilingContentValue, EDGAR
     FilingContentValue::new(entry_content.clone())
 }
 /// Returns a client that can send requests to EDGAR.
-/// Please define the `USER_AGENT` in your environment variables.
+/// The user agent is read from the `USER_AGENT` environment variable at runtime, falling back to its value at compile time.
 /// [According to the SEC](https://www.sec.gov/os/webmaster-faq#developers), the `USER_AGENT` must be in the form:
 /// ```txt
 /// Sample Company Name AdminContact@<sample company domain>.com
 /// ```
 /// For Rust apps, I recommend defining it in [`/your_project/.cargo/config.toml`](https://doc.rust-lang.org/cargo/reference/config.html#hierarchical-structure)
 ///
+/// To set the user agent, timeouts, a proxy or extra headers at runtime, use [EdgarClientBuilder] instead.
+///
 /// Usage:
 /// ```
 /// use sec_edgar::edgar::edgar_client;
 /// let client = edgar_client();
 /// ```
-pub fn edgar_client() -> Result<Client, EDGARError> {
-    let mut headers = HeaderMap::new();
-    let user_agent = match option_env!("USER_AGENT") {
-        Some(u) => u,
-        None => return Err(EDGARError::UserAgentEnvVarMissing),
-    };
-    headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
-    headers.insert(HOST, HeaderValue::from_static("www.sec.gov"));
-    headers.insert(USER_AGENT, HeaderValue::from_static(user_agent));
-    Ok(Client::builder()
-        .default_headers(headers)
-        .deflate(true)
-        .gzip(true)
-        .build()?)
+pub fn edgar_client() -> Result<EdgarClient, EDGARError> {
+    EdgarClientBuilder::new().build()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::{
+        endpoints::Endpoints,
+        test_server::{client, TestResponse, TestServer},
+        transport::ReplayTransport,
+    };
     use crate::edgar_query::{
         cik_query::CIKQuery,
         edgar_query_builder::{BuilderInput, EdgarQueryBuilder},
         filing::FilingTypeOption::_10Q,
     };
 
-    async fn edgar_sample_query_ending(cik_query: String) {
+    async fn edgar_sample_query_ending(client: EdgarClient, cik_query: String) {
         let answer = "10-Q";
         let query = EdgarQueryBuilder::new(&cik_query)
             .set_filing_type(BuilderInput::TypeTInput(_10Q))
             .build()
             .unwrap();
-        let entries = get_feed_entries(edgar_client().unwrap(), query).await;
+        let entries = get_feed_entries(client, query).await;
         let filing_type = get_feed_entry_content(entries.unwrap().first().unwrap())
             .unwrap()
             .filing_type
@@ -191,6 +385,137 @@ mod tests {
         assert_eq!(filing_type, answer);
     }
     #[tokio::test]
+    async fn edgar_sample_query_replay() {
+        let client = EdgarClientBuilder::new()
+            .set_user_agent("Sample Company Name admin@samplecompany.com")
+            .set_transport(ReplayTransport::replay("./tests/fixtures/replay"))
+            .build()
+            .unwrap();
+        let cik_query = CIKQuery::new(None)
+            .unwrap()
+            .get_cik_with_client(client.clone(), "c")
+            .await
+            .expect("ticker not found");
+        edgar_sample_query_ending(client, cik_query).await
+    }
+    #[tokio::test]
+    async fn edgar_sample_query_endpoint() {
+        let server = TestServer::start().route(
+            "/cgi-bin/browse-edgar",
+            vec![TestResponse::ok(
+                "application/atom+xml",
+                include_bytes!("../tests/fixtures/browse_edgar_831001_10q.xml"),
+            )],
+        );
+        let endpoints = Endpoints::from_base(server.base());
+        let client = client(&server);
+        let query = EdgarQueryBuilder::new("831001")
+            .set_endpoints(&endpoints)
+            .set_filing_type(BuilderInput::TypeTInput(_10Q))
+            .build()
+            .unwrap();
+        let entries = get_feed_entries(client, query).await.unwrap();
+        let content = get_feed_entry_content(entries.first().unwrap()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(content.filing_type.value, "10-Q");
+        assert!(server.requests()[0][0].contains("CIK=0000831001&type=10-Q"))
+    }
+    #[tokio::test]
+    async fn edgar_company_info_replay() {
+        let client = EdgarClientBuilder::new()
+            .set_user_agent("Sample Company Name admin@samplecompany.com")
+            .set_transport(ReplayTransport::replay("./tests/fixtures/replay"))
+            .build()
+            .unwrap();
+        let query = EdgarQueryBuilder::new("831001")
+            .set_filing_type(BuilderInput::TypeTInput(_10Q))
+            .build()
+            .unwrap();
+        let company_info = get_company_info(client, query).await.unwrap();
+        assert_eq!(company_info.conformed_name, "CITIGROUP INC");
+        assert_eq!(company_info.former_names.len(), 2)
+    }
+    /// The sample feed with its second, older entry removed.
+    fn first_entry_only_feed() -> String {
+        let feed = include_str!("../tests/fixtures/browse_edgar_831001_10q.xml");
+        let second = feed.match_indices("<entry>").nth(1).unwrap().0;
+        let end = feed.rfind("</entry>").unwrap() + "</entry>".len();
+        format!("{}{}", &feed[..second], &feed[end..])
+    }
+    fn paging_client(server: &TestServer) -> (EdgarClient, EdgarQueryBuilder) {
+        let endpoints = Endpoints::from_base(server.base());
+        let client = client(server);
+        let query = EdgarQueryBuilder::new("831001")
+            .set_endpoints(&endpoints)
+            .set_count("2");
+        (client, query)
+    }
+    #[tokio::test]
+    async fn edgar_all_feed_entries_until_short_page() {
+        let full = include_bytes!("../tests/fixtures/browse_edgar_831001_10q.xml");
+        let short = first_entry_only_feed();
+        let server = TestServer::start().route(
+            "/cgi-bin/browse-edgar",
+            vec![
+                TestResponse::ok("application/atom+xml", full),
+                TestResponse::ok("application/atom+xml", short.as_bytes()),
+            ],
+        );
+        let (client, query) = paging_client(&server);
+        let entries = get_all_feed_entries(client, query, None).await.unwrap();
+        let requests = server.requests();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0][0].contains("&start=0&"));
+        assert!(requests[1][0].contains("&start=2&"))
+    }
+    #[tokio::test]
+    async fn edgar_all_feed_entries_filed_since() {
+        let server = TestServer::start().route(
+            "/cgi-bin/browse-edgar",
+            vec![TestResponse::ok(
+                "application/atom+xml",
+                include_bytes!("../tests/fixtures/browse_edgar_831001_10q.xml"),
+            )],
+        );
+        let (client, query) = paging_client(&server);
+        let since = NaiveDate::from_ymd_opt(2023, 6, 1);
+        let entries = get_all_feed_entries(client, query, since).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(server.requests().len(), 1)
+    }
+    #[tokio::test]
+    async fn edgar_stream_filings_is_lazy() {
+        let server = TestServer::start().route(
+            "/cgi-bin/browse-edgar",
+            vec![TestResponse::ok(
+                "application/atom+xml",
+                include_bytes!("../tests/fixtures/browse_edgar_831001_10q.xml"),
+            )],
+        );
+        let (client, query) = paging_client(&server);
+        let filings: Vec<FeedFiling> = stream_filings(client, query, None)
+            .take(3)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(filings.len(), 3);
+        assert_eq!(
+            filings[1].content.accession_number.value,
+            "0000831001-23-000089"
+        );
+        assert_eq!(server.requests().len(), 2)
+    }
+    #[tokio::test]
+    async fn edgar_stream_feed_entries_ends_after_error() {
+        let server =
+            TestServer::start().route("/cgi-bin/browse-edgar", vec![TestResponse::status(404)]);
+        let (client, query) = paging_client(&server);
+        let results: Vec<_> = stream_feed_entries(client, query, None).collect().await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err())
+    }
+    #[tokio::test]
     #[ignore = "Tests with local file. The file could be put anywhere."]
     async fn edgar_sample_query_local_file() {
         let ticker = "c";
@@ -199,7 +524,7 @@ mod tests {
             .get_cik(ticker)
             .await
             .expect("ticker not found");
-        edgar_sample_query_ending(cik_query).await
+        edgar_sample_query_ending(edgar_client().unwrap(), cik_query).await
     }
     #[tokio::test]
     async fn edgar_sample_query_web() {
@@ -209,6 +534,6 @@ mod tests {
             .get_cik(ticker)
             .await
             .expect("ticker not found");
-        edgar_sample_query_ending(cik_query).await
+        edgar_sample_query_ending(edgar_client().unwrap(), cik_query).await
     }
 }
@@ -1,5 +1,6 @@
 //! This module contains the query builder for EDGAR queries.
 pub mod cik_query;
+pub mod company_info;
 pub mod edgar_query_builder;
 pub mod filing_content_value;
 pub mod filing;
@@ -1,9 +1,9 @@
 //! This module provides functions to get the CIK from a ticker symbol.
 
+use crate::client::EdgarClient;
 use crate::edgar::edgar_client;
 use crate::error::EDGARError;
 use regex::Regex;
-use reqwest::Url;
 use std::io::BufRead;
 use std::{
     fs::File,
@@ -11,14 +11,15 @@ use std::{
     path::{Path, PathBuf},
 };
 
-const TICKER_URL: &str = "https://www.sec.gov/include/ticker.txt";
+/// The path of the ticker file on the `www.sec.gov` endpoint.
+const TICKER_PATH: &str = "include/ticker.txt";
 /// The input of `fn new()` for [CIKQuery]
 #[derive(Debug, PartialEq)]
 enum CIKDictionaryLocation {
     /// The path of the file containing [this info](https://www.sec.gov/include/ticker.txt).
     FilePath(PathBuf),
-    /// The default Url of [this](https://www.sec.gov/include/ticker.txt).
-    Url(Url),
+    /// [This file](https://www.sec.gov/include/ticker.txt), found on the `www.sec.gov` endpoint of the client.
+    Web,
 }
 /// Provides a methods to get short CIKs (no leading zeros) from a ticker represented as string.
 #[derive(Debug, PartialEq)]
@@ -53,12 +54,36 @@ impl CIKQuery {
     /// }
     /// ```
     /// The ticker is **case-insensitive**.
+    ///
+    /// The default client from [edgar_client] is used when querying the web. See [CIKQuery::get_cik_with_client] to use your own.
     pub async fn get_cik(&self, ticker: &str) -> Result<String, EDGARError> {
-        let ticker_low = ticker.to_lowercase();
         match &self.location {
-            CIKDictionaryLocation::Url(location) => {
-                get_cik_from_web(location, ticker_low.as_str()).await
+            CIKDictionaryLocation::Web => self.get_cik_with_client(edgar_client()?, ticker).await,
+            CIKDictionaryLocation::FilePath(location) => {
+                get_cik_from_file(location, ticker.to_lowercase().as_str())
             }
+        }
+    }
+    /// Same as [CIKQuery::get_cik], but the `client` is used when querying the web.
+    /// The ticker file is fetched from the `www.sec.gov` endpoint of the `client`.
+    /// ```
+    /// use sec_edgar::{client::EdgarClientBuilder, edgar_query::cik_query::CIKQuery};
+    /// async fn some_func() {
+    ///     let client = EdgarClientBuilder::new()
+    ///         .set_user_agent("Sample Company Name admin@samplecompany.com")
+    ///         .build()
+    ///         .unwrap();
+    ///     let cik = CIKQuery::new(None).unwrap().get_cik_with_client(client, "AMD").await;
+    /// }
+    /// ```
+    pub async fn get_cik_with_client(
+        &self,
+        client: EdgarClient,
+        ticker: &str,
+    ) -> Result<String, EDGARError> {
+        let ticker_low = ticker.to_lowercase();
+        match &self.location {
+            CIKDictionaryLocation::Web => get_cik_from_web(client, ticker_low.as_str()).await,
             CIKDictionaryLocation::FilePath(location) => {
                 get_cik_from_file(location, ticker_low.as_str())
             }
@@ -72,12 +97,13 @@ fn ticker_file_location(path: &str) -> CIKQuery {
     }
 }
 fn default_ticker_url_location() -> Result<CIKQuery, EDGARError> {
-    let location = CIKDictionaryLocation::Url(Url::parse(TICKER_URL)?);
-    Ok(CIKQuery { location })
+    Ok(CIKQuery {
+        location: CIKDictionaryLocation::Web,
+    })
 }
-async fn get_cik_from_web(location: &Url, ticker: &str) -> Result<String, EDGARError> {
-    let response = edgar_client()?.get(location.as_str()).send().await?;
-    let body = response.text().await?;
+async fn get_cik_from_web(client: EdgarClient, ticker: &str) -> Result<String, EDGARError> {
+    let location = client.endpoints().www_url(TICKER_PATH)?;
+    let body = client.get_text(&location).await?;
     find_cik_from_html(body.as_str(), ticker)
 }
 fn get_cik_from_file(location: &Path, ticker: &str) -> Result<String, EDGARError> {
@@ -118,6 +144,11 @@ fn find_cik_from_line(line: &str, ticker: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::{
+        test_server::{client, TestResponse, TestServer},
+        transport::ReplayTransport,
+        EdgarClientBuilder,
+    };
     #[test]
     #[ignore = r"The file could be placed anywhere. By default it will check ./ticker.txt. The file comes from:
     https://www.sec.gov/include/ticker.txt"]
@@ -163,6 +194,35 @@ mod tests {
         assert_eq!(res.unwrap().as_str(), answer)
     }
     #[tokio::test]
+    async fn cik_query_get_cik_from_replay() {
+        let client = EdgarClientBuilder::new()
+            .set_user_agent("Sample Company Name admin@samplecompany.com")
+            .set_transport(ReplayTransport::replay("./tests/fixtures/replay"))
+            .build()
+            .unwrap();
+        let res = CIKQuery::new(None)
+            .unwrap()
+            .get_cik_with_client(client, "c")
+            .await;
+        assert_eq!(res.unwrap().as_str(), "831001")
+    }
+    #[tokio::test]
+    async fn cik_query_get_cik_from_endpoint() {
+        let server = TestServer::start().route(
+            "/include/ticker.txt",
+            vec![TestResponse::ok(
+                "text/plain",
+                include_bytes!("../../tests/fixtures/ticker.txt"),
+            )],
+        );
+        let client = client(&server);
+        let res = CIKQuery::new(None)
+            .unwrap()
+            .get_cik_with_client(client, "C")
+            .await;
+        assert_eq!(res.unwrap().as_str(), "831001")
+    }
+    #[tokio::test]
     // #[ignore = "Expensive test and must be connected to the internet"]
     async fn cik_query_get_cik_from_web() {
         let answer = "831001";
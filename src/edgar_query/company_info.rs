@@ -0,0 +1,279 @@
+//! This module is used for extracting the company information that precedes the entries of a feed.
+//!
+//! From `https://www.sec.gov/cgi-bin/browse-edgar?action=getcompany&CIK=0000831001&type=10-Q&count=10&output=atom`
+//! ```xml
+//! <company-info>
+//!     <addresses>
+//!         <address type="mailing">
+//!             <city>NEW YORK</city>
+//!             <state>NY</state>
+//!             <street1>388 GREENWICH STREET</street1>
+//!             <zip>10013</zip>
+//!         </address>
+//!     <!-- snip -->
+//!     </addresses>
+//!     <cik>0000831001</cik>
+//!     <conformed-name>CITIGROUP INC</conformed-name>
+//!     <fiscal-year-end>1231</fiscal-year-end>
+//!     <formerly-names>
+//!         <names>
+//!             <date>1998-10-08</date>
+//!             <name>TRAVELERS GROUP INC</name>
+//!         </names>
+//!     </formerly-names>
+//! <!-- snip -->
+//! </company-info>
+//! ```
+use crate::error::EDGARError;
+use crate::fields::{date, invalid_field, non_empty};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_xml_rs::from_str;
+use std::str::FromStr;
+
+/// The kind of an [Address].
+#[derive(Clone, Debug, PartialEq)]
+pub enum AddressType {
+    /// Where the company does business.
+    Business,
+    /// Where the company receives mail.
+    Mailing,
+    /// Any other kind of address, as written in the feed.
+    Other(String),
+}
+/// One of the addresses of a company.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Address {
+    /// See [AddressType]
+    pub address_type: AddressType,
+    /// The first line of the street address.
+    pub street1: Option<String>,
+    /// The second line of the street address.
+    pub street2: Option<String>,
+    /// The city.
+    pub city: Option<String>,
+    /// The state or country code.
+    pub state: Option<String>,
+    /// The ZIP code.
+    pub zip: Option<String>,
+    /// The phone number, usually only given for the business address.
+    pub phone: Option<String>,
+}
+/// A name the company was previously known by.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormerName {
+    /// The former name.
+    pub name: String,
+    /// The date the name was changed.
+    pub date: Option<NaiveDate>,
+}
+/// The last day of a company's fiscal year.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FiscalYearEnd {
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// The day of the month, from 1 to 31.
+    pub day: u32,
+}
+impl FromStr for FiscalYearEnd {
+    type Err = EDGARError;
+    /// Parses a fiscal year end written as `MMDD`, such as `1231`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fiscal_year_end(s).ok_or_else(|| invalid_field("fiscal year end", s))
+    }
+}
+/// Provides structure for the `<company-info>` block of a feed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompanyInfo {
+    /// The CIK with leading zeros.
+    pub cik: String,
+    /// The official name of the company.
+    pub conformed_name: String,
+    /// See [FiscalYearEnd]
+    pub fiscal_year_end: Option<FiscalYearEnd>,
+    /// The SEC office that reviews the company's filings.
+    pub office: Option<String>,
+    /// The state where the company is located.
+    pub state_location: Option<String>,
+    /// The state where the company is incorporated.
+    pub state_of_incorporation: Option<String>,
+    /// The Standard Industrial Classification code.
+    pub sic: Option<u16>,
+    /// The description of the Standard Industrial Classification code.
+    pub sic_description: Option<String>,
+    /// See [Address]
+    pub addresses: Vec<Address>,
+    /// See [FormerName]
+    pub former_names: Vec<FormerName>,
+}
+impl CompanyInfo {
+    /// Instantiates [CompanyInfo] from the text of a feed with atom format.
+    ///
+    /// The text is required because the `<company-info>` block is not part of the atom format and is dropped when a [Feed](atom_syndication::Feed) is parsed.
+    /// ```
+    /// use sec_edgar::edgar_query::company_info::CompanyInfo;
+    /// let feed = "<feed><company-info><cik>0000831001</cik><conformed-name>CITIGROUP INC</conformed-name></company-info></feed>";
+    /// let company_info = CompanyInfo::new(feed).unwrap();
+    /// assert_eq!(company_info.conformed_name, "CITIGROUP INC");
+    /// ```
+    pub fn new(feed: &str) -> Result<Self, EDGARError> {
+        let start = feed
+            .find("<company-info>")
+            .ok_or(EDGARError::CompanyInfoNotFound)?;
+        let end = feed[start..]
+            .find("</company-info>")
+            .ok_or(EDGARError::CompanyInfoNotFound)?
+            + start
+            + "</company-info>".len();
+        let raw: RawCompanyInfo = from_str(&feed[start..end])?;
+        Ok(raw.into())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCompanyInfo {
+    cik: String,
+    #[serde(rename = "conformed-name")]
+    conformed_name: String,
+    #[serde(rename = "fiscal-year-end")]
+    fiscal_year_end: Option<String>,
+    office: Option<String>,
+    #[serde(rename = "state-location")]
+    state_location: Option<String>,
+    #[serde(rename = "state-of-incorporation")]
+    state_of_incorporation: Option<String>,
+    sic: Option<String>,
+    #[serde(rename = "sic-description")]
+    sic_description: Option<String>,
+    addresses: Option<RawAddresses>,
+    #[serde(rename = "formerly-names")]
+    formerly_names: Option<RawFormerlyNames>,
+}
+#[derive(Debug, Deserialize)]
+struct RawAddresses {
+    #[serde(default)]
+    address: Vec<RawAddress>,
+}
+#[derive(Debug, Deserialize)]
+struct RawAddress {
+    #[serde(rename = "type")]
+    address_type: String,
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    zip: Option<String>,
+    phone: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFormerlyNames {
+    #[serde(default)]
+    names: Vec<RawFormerName>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFormerName {
+    date: Option<String>,
+    name: String,
+}
+impl From<RawCompanyInfo> for CompanyInfo {
+    fn from(raw: RawCompanyInfo) -> Self {
+        Self {
+            cik: raw.cik,
+            conformed_name: raw.conformed_name,
+            fiscal_year_end: raw.fiscal_year_end.as_deref().and_then(fiscal_year_end),
+            office: non_empty(raw.office),
+            state_location: non_empty(raw.state_location),
+            state_of_incorporation: non_empty(raw.state_of_incorporation),
+            sic: raw.sic.and_then(|s| s.trim().parse().ok()),
+            sic_description: non_empty(raw.sic_description),
+            addresses: raw
+                .addresses
+                .map(|a| a.address)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| Address {
+                    address_type: match a.address_type.to_lowercase().as_str() {
+                        "business" => AddressType::Business,
+                        "mailing" => AddressType::Mailing,
+                        _ => AddressType::Other(a.address_type),
+                    },
+                    street1: non_empty(a.street1),
+                    street2: non_empty(a.street2),
+                    city: non_empty(a.city),
+                    state: non_empty(a.state),
+                    zip: non_empty(a.zip),
+                    phone: non_empty(a.phone),
+                })
+                .collect(),
+            former_names: raw
+                .formerly_names
+                .map(|f| f.names)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|f| FormerName {
+                    name: f.name,
+                    date: f.date.as_deref().and_then(|d| date(d).ok()),
+                })
+                .collect(),
+        }
+    }
+}
+/// The fiscal year end is written as `MMDD`.
+fn fiscal_year_end(mmdd: &str) -> Option<FiscalYearEnd> {
+    let mmdd = mmdd.trim();
+    if mmdd.len() != 4 || !mmdd.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let month = mmdd[..2].parse().ok().filter(|m| (1..=12).contains(m))?;
+    let day = mmdd[2..].parse().ok().filter(|d| (1..=31).contains(d))?;
+    Some(FiscalYearEnd { month, day })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn company_info_from_feed() {
+        let feed = include_str!("../../tests/fixtures/browse_edgar_831001_10q.xml");
+        let company_info = CompanyInfo::new(feed).unwrap();
+        assert_eq!(company_info.cik, "0000831001");
+        assert_eq!(company_info.conformed_name, "CITIGROUP INC");
+        assert_eq!(
+            company_info.fiscal_year_end,
+            Some(FiscalYearEnd { month: 12, day: 31 })
+        );
+        assert_eq!(company_info.office.as_deref(), Some("Office of Finance"));
+        assert_eq!(company_info.sic, Some(6021));
+        assert_eq!(company_info.state_of_incorporation.as_deref(), Some("DE"));
+        assert_eq!(company_info.addresses.len(), 2);
+        let business = &company_info.addresses[1];
+        assert_eq!(business.address_type, AddressType::Business);
+        assert_eq!(business.phone.as_deref(), Some("2125591000"));
+        assert_eq!(
+            company_info.former_names[0],
+            FormerName {
+                name: "TRAVELERS GROUP INC".to_string(),
+                date: NaiveDate::from_ymd_opt(1998, 10, 8),
+            }
+        )
+    }
+    #[test]
+    fn company_info_missing() {
+        let res = CompanyInfo::new("<feed></feed>");
+        assert!(matches!(res, Err(EDGARError::CompanyInfoNotFound)))
+    }
+    #[test]
+    fn company_info_fiscal_year_end() {
+        assert_eq!(
+            fiscal_year_end("0630"),
+            Some(FiscalYearEnd { month: 6, day: 30 })
+        );
+        assert_eq!(fiscal_year_end("1331"), None);
+        assert_eq!(fiscal_year_end("1é1"), None);
+        assert!(matches!(
+            "1é1".parse::<FiscalYearEnd>(),
+            Err(EDGARError::InvalidFieldValue { .. })
+        ))
+    }
+}
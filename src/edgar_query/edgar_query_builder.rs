@@ -1,6 +1,6 @@
 //! This module provides a way to build the URL query that will be used to query EDGAR.
 
-use crate::error::EDGARError;
+use crate::{client::endpoints::Endpoints, error::EDGARError};
 
 use super::{
     filing::{self, validate_filing_type_string, FilingTypeOption},
@@ -23,7 +23,7 @@ pub enum BuilderInput<'a, T> {
 ///     .build()
 ///     .unwrap();
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EdgarQueryBuilder {
     #[allow(missing_docs)]
     pub base: String,
@@ -39,6 +39,8 @@ pub struct EdgarQueryBuilder {
     pub count: String,
     #[allow(missing_docs)]
     pub search_text: String,
+    #[allow(missing_docs)]
+    pub start: String,
 }
 impl EdgarQueryBuilder {
     /// Instantiating a query builder with the following defaults:
@@ -56,12 +58,15 @@ impl EdgarQueryBuilder {
     ///     dateb: default.clone(),
     ///     owner: "include".to_string(),
     ///     count: "10".to_string(),
-    ///     search_text: default,
+    ///     search_text: default.clone(),
+    ///     start: default,
     /// };
     /// ```
     /// It is assumed that the CIK is valid.
+    ///
+    /// To query somewhere other than the SEC, see [EdgarQueryBuilder::set_endpoints].
     pub fn new(short_cik: &str) -> Self {
-        let base = "https://www.sec.gov/cgi-bin/browse-edgar?action=getcompany&".to_string();
+        let base = browse_edgar_base(&Endpoints::default());
         let cik = add_leading_zeros_to_cik(short_cik);
         Self {
             base,
@@ -71,11 +76,16 @@ impl EdgarQueryBuilder {
             owner: "include".to_string(),
             count: "10".to_string(),
             search_text: Default::default(),
+            start: Default::default(),
         }
     }
     /// Builds and returns the raw HTTPS query that can be used to query EDGAR.
     pub fn build(&self) -> Result<Url, EDGARError> {
-        let url_res = format!("{base}CIK={cik}&type={filing_type}&dateb={dateb}&owner={owner}&count={count}&search_text={search_text}&output=atom",
+        let start = match self.start.as_str() {
+            "" => String::new(),
+            start => format!("&start={start}"),
+        };
+        let url_res = format!("{base}CIK={cik}&type={filing_type}&dateb={dateb}&owner={owner}&count={count}&search_text={search_text}{start}&output=atom",
             base = self.base,
             cik = self.cik,
             filing_type = self.filing_type,
@@ -113,6 +123,7 @@ impl EdgarQueryBuilder {
     /// - "include" means include all documents regardless of the source.
     /// - "exclude" means exclude documents related to the company's director or officer ownership.
     /// - "only" means only show documents related to the company's director or officer ownership.
+    ///
     /// If owner is not set, the default is "include".
     pub fn set_owner(mut self, owner: BuilderInput<OwnerOptions>) -> Self {
         self.owner = match owner {
@@ -136,6 +147,31 @@ impl EdgarQueryBuilder {
         self.count = count.to_string();
         self
     }
+    /// The number of the most recent filings to skip, which allows paging through filings beyond the first `count`.
+    ///
+    /// For example, with a count of 100, a start of "100" returns the 101st to the 200th most recent filings.
+    ///
+    /// If start is not set, the default is an empty String, which is interpreted as 0 by EDGAR.
+    /// To walk every page, see [get_all_feed_entries](crate::edgar::get_all_feed_entries).
+    pub fn set_start(mut self, start: &str) -> Self {
+        self.start = start.to_string();
+        self
+    }
+    /// Queries the browse-edgar service of `endpoints` rather than the SEC's.
+    /// ```
+    /// use sec_edgar::{
+    ///     client::endpoints::Endpoints,
+    ///     edgar_query::edgar_query_builder::EdgarQueryBuilder,
+    /// };
+    /// use url::Url;
+    /// let endpoints = Endpoints::from_base(&Url::parse("http://127.0.0.1:8080").unwrap());
+    /// let query = EdgarQueryBuilder::new("78003").set_endpoints(&endpoints);
+    /// assert_eq!(query.base, "http://127.0.0.1:8080/cgi-bin/browse-edgar?action=getcompany&");
+    /// ```
+    pub fn set_endpoints(mut self, endpoints: &Endpoints) -> Self {
+        self.base = browse_edgar_base(endpoints);
+        self
+    }
     /// If search text is not set, the default is an empty string.
     pub fn set_search_text(mut self, search_text: &str) -> Self {
         self.search_text = search_text.to_string();
@@ -143,6 +179,10 @@ impl EdgarQueryBuilder {
     }
 }
 
+fn browse_edgar_base(endpoints: &Endpoints) -> String {
+    format!("{}cgi-bin/browse-edgar?action=getcompany&", endpoints.www())
+}
+
 /// EDGAR queries require a CIK with ten digits, however, most CIKs have less than ten digits.
 /// Leading zeros must be added to the CIK to reach this ten digit requirement.
 pub fn add_leading_zeros_to_cik(cik: &str) -> String {
@@ -196,6 +236,11 @@ mod tests {
         assert_eq!(query.count.as_str(), "10")
     }
     #[test]
+    fn edgar_query_builder_set_start() {
+        let query = sample().set_start("100").build().unwrap();
+        assert!(query.as_str().ends_with("&search_text=&start=100&output=atom"))
+    }
+    #[test]
     fn edgar_query_builder_build() {
         let answer = "https://www.sec.gov/cgi-bin/browse-edgar?action=getcompany&CIK=0000078003&type=10-k&dateb=&owner=include&count=20&search_text=&output=atom".to_lowercase();
         let query = sample()
@@ -6,7 +6,7 @@ use crate::error::EDGARError;
 
 /// Filing types taken from [this site](https://www.sec.gov/forms)
 #[allow(missing_docs)]
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FilingTypeOption {
     _1,
     _10,
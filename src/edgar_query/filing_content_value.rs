@@ -1,22 +1,123 @@
 //! This module is used for extracting the content type of a filing.
+//!
+//! Fields with a natural type, such as the filing date or the size, are parsed when the content is deserialized.
+//! The raw string of each field remains available as its `value`.
+use crate::edgar_query::filing::{filing_from_str, FilingTypeOption};
 use crate::error::EDGARError;
+use crate::fields::invalid_field;
 use atom_syndication::Content;
+use chrono::NaiveDate;
 use serde::Deserialize;
 use serde_xml_rs::from_str;
+use std::str::FromStr;
+use url::Url;
 
-/// The Unique identifier assigned to each filing.
+/// The text of an XML element, deserialized before it is converted to a typed field.
+#[derive(Deserialize)]
+struct Text {
+    #[serde(rename = "$value", default)]
+    value: String,
+}
+
+/// The Unique identifier assigned to each filing, in the form `0000831001-23-000114`.
+/// ```
+/// use sec_edgar::edgar_query::filing_content_value::AccessionNumber;
+/// let accession_number: AccessionNumber = "0000831001-23-000114".parse().unwrap();
+/// assert_eq!(accession_number.filer_id, "0000831001");
+/// assert_eq!(accession_number.year, 23);
+/// assert_eq!(accession_number.sequence, 114);
+/// assert_eq!(accession_number.no_dashes(), "000083100123000114");
+/// ```
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "Text")]
 pub struct AccessionNumber {
     /// value of AccessionNumber
-    #[serde(rename = "$value")]
     pub value: String,
+    /// The CIK of the entity that submitted the filing, which may be a filing agent rather than the company.
+    pub filer_id: String,
+    /// The last two digits of the year the filing was submitted in.
+    pub year: u8,
+    /// The sequence number of the filing among those submitted by the filer that year.
+    pub sequence: u32,
+}
+impl AccessionNumber {
+    /// The accession number without dashes, as it appears in the paths of the archives.
+    pub fn no_dashes(&self) -> String {
+        self.value.replace('-', "")
+    }
+}
+impl FromStr for AccessionNumber {
+    type Err = EDGARError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let invalid = || invalid_field("accession number", value);
+        let mut parts = value.split('-');
+        let (Some(filer_id), Some(year), Some(sequence), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if filer_id.len() != 10 || !filer_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if year.len() != 2 || sequence.len() != 6 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            value: value.to_string(),
+            filer_id: filer_id.to_string(),
+            year: year.parse().map_err(|_| invalid())?,
+            sequence: sequence.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+impl TryFrom<Text> for AccessionNumber {
+    type Error = EDGARError;
+    fn try_from(text: Text) -> Result<Self, Self::Error> {
+        text.value.parse()
+    }
+}
+/// The legislation under which a filing is made.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActOption {
+    /// The Securities Act of 1933, written as `33`.
+    SecuritiesAct1933,
+    /// The Securities Exchange Act of 1934, written as `34`.
+    ExchangeAct1934,
+    /// The Trust Indenture Act of 1939, written as `39`.
+    TrustIndentureAct1939,
+    /// The Investment Company Act of 1940, written as `40`.
+    InvestmentCompanyAct1940,
+    /// Any other act, or none, as written in the feed.
+    Other(String),
+}
+impl From<&str> for ActOption {
+    fn from(act: &str) -> Self {
+        match act.trim() {
+            "33" => Self::SecuritiesAct1933,
+            "34" => Self::ExchangeAct1934,
+            "39" => Self::TrustIndentureAct1939,
+            "40" => Self::InvestmentCompanyAct1940,
+            other => Self::Other(other.to_string()),
+        }
+    }
 }
 /// The specific legislation or law under which a filing is made.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(from = "Text")]
 pub struct Act {
     /// value of Act
-    #[serde(rename = "$value")]
     pub value: String,
+    /// See [ActOption]
+    pub act: ActOption,
+}
+impl From<Text> for Act {
+    fn from(text: Text) -> Self {
+        Self {
+            act: ActOption::from(text.value.as_str()),
+            value: text.value,
+        }
+    }
 }
 /// A unique number assigned to each filing and helps in tracking and referencing specific submissions.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -30,28 +131,52 @@ pub struct FileNumber {
 pub struct FileNumberHref {
     /// value of FileNumberHref
     #[serde(rename = "$value")]
-    pub value: String,
+    pub value: Url,
 }
 /// The date at which the filing was made.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "Text")]
 pub struct FilingDate {
     /// value of FilingDate
-    #[serde(rename = "$value")]
     pub value: String,
+    /// The parsed calendar date.
+    pub date: NaiveDate,
 }
-/// The filing link
+impl TryFrom<Text> for FilingDate {
+    type Error = EDGARError;
+    fn try_from(text: Text) -> Result<Self, Self::Error> {
+        let date = NaiveDate::parse_from_str(text.value.trim(), "%Y-%m-%d")
+            .map_err(|_| invalid_field("filing date", &text.value))?;
+        Ok(Self {
+            value: text.value,
+            date,
+        })
+    }
+}
+/// The link to the filing index, which lists every document of the filing.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct FilingHref {
     /// value of FilingHref
     #[serde(rename = "$value")]
-    pub value: String,
+    pub value: Url,
 }
 /// The Filing Type.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(from = "Text")]
 pub struct FilingType {
     /// value of FilingType
-    #[serde(rename = "$value")]
     pub value: String,
+    /// The [FilingTypeOption] of the filing.
+    /// None for amendments such as `10-Q/A` and for filing types that have no [FilingTypeOption].
+    pub filing_type: Option<FilingTypeOption>,
+}
+impl From<Text> for FilingType {
+    fn from(text: Text) -> Self {
+        Self {
+            filing_type: filing_from_str(text.value.trim()).ok(),
+            value: text.value,
+        }
+    }
 }
 /// A unique identifier assigned to the microfilm version of a filing.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -67,22 +192,43 @@ pub struct FormName {
     #[serde(rename = "$value")]
     pub value: String,
 }
-/// Human-readable file size.
+/// Human-readable file size, such as `7 MB`.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "Text")]
 pub struct Size {
     /// value of Size
-    #[serde(rename = "$value")]
     pub value: String,
+    /// The approximate size in bytes, counting a KB as 1024 bytes.
+    /// The precision is limited to the rounding of the human-readable size.
+    pub bytes: u64,
+}
+impl TryFrom<Text> for Size {
+    type Error = EDGARError;
+    fn try_from(text: Text) -> Result<Self, Self::Error> {
+        let invalid = || invalid_field("size", &text.value);
+        let (number, unit) = text.value.trim().split_once(' ').ok_or_else(invalid)?;
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        let multiplier: u64 = match unit.trim().to_uppercase().as_str() {
+            "B" | "BYTE" | "BYTES" => 1,
+            "KB" => 1 << 10,
+            "MB" => 1 << 20,
+            "GB" => 1 << 30,
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            bytes: (number * multiplier as f64).round() as u64,
+            value: text.value,
+        })
+    }
 }
-/// An ancient alien language.
+/// The link to the XBRL viewer of the filing. Only filings with XBRL financial data have one.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct XbrlHref {
     /// value of XbrlHref
     #[serde(rename = "$value")]
-    pub value: String,
+    pub value: Url,
 }
 /// Provides structure for the feed's entry's content, which is in the form of Some("string") in Rust.
-/// Href values are not provided because the serde_xml_rs crate cannot deserialize values that contain the `=` symbol.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct FilingContentValue {
     /// See [AccessionNumber]
@@ -93,9 +239,15 @@ pub struct FilingContentValue {
     /// See [FileNumber]
     #[serde(rename = "file-number")]
     pub file_number: FileNumber,
+    /// See [FileNumberHref]
+    #[serde(rename = "file-number-href")]
+    pub file_number_href: Option<FileNumberHref>,
     /// See [FilingDate]
     #[serde(rename = "filing-date")]
     pub filing_date: FilingDate,
+    /// See [FilingHref]
+    #[serde(rename = "filing-href")]
+    pub filing_href: FilingHref,
     #[serde(rename = "filing-type")]
     /// See [FilingType]
     pub filing_type: FilingType,
@@ -107,30 +259,21 @@ pub struct FilingContentValue {
     pub form_name: FormName,
     /// See [Size]
     pub size: Size,
-    // Might be able to parse someday.
-    // The serde_xml_rs::from_str function fails for deserializing values that contain "=" sign.
-    // This might an easy fix later.
-    // #[serde(rename = "file-name-href")]
-    // pub file_number_href: FileNumberHref,
-    // #[serde(rename = "filing-href")]
-    // pub filing_href: FilingHref,
-    // #[serde(rename = "xbrl-href")]
-    // pub xbrl_href: XbrlHref,
+    /// See [XbrlHref]
+    #[serde(rename = "xbrl_href")]
+    pub xbrl_href: Option<XbrlHref>,
 }
 
 impl FilingContentValue {
     /// Instantiates the [FilingContentValue] to deserialize the content of an entry from a feed with atom format.
+    ///
+    /// The feed parser unescapes the content, which leaves bare `&` in the query strings of the href values.
+    /// Those are escaped again before the content is deserialized.
     pub fn new(content: Content) -> Result<Self, EDGARError> {
         let value = content
             .value
             .ok_or(EDGARError::FilingContentValueNotFound)?;
-        let mut processed_values = value
-            .split('\n')
-            .collect::<Vec<&str>>()
-            .into_iter()
-            .filter(|&line| !line.contains("href"))
-            .collect::<Vec<&str>>()
-            .join("");
+        let mut processed_values = escape_bare_ampersands(&value);
         processed_values.insert_str(0, "<ContentValue>");
         processed_values.push_str("</ContentValue>");
         let filing: Self = from_str(processed_values.as_str())?;
@@ -138,6 +281,33 @@ impl FilingContentValue {
     }
 }
 
+/// Escapes every `&` that does not start an entity or character reference.
+fn escape_bare_ampersands(xml: &str) -> String {
+    let mut escaped = String::with_capacity(xml.len());
+    for (i, c) in xml.char_indices() {
+        if c == '&' && !starts_with_reference(&xml[i + 1..]) {
+            escaped.push_str("&amp;");
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+/// Whether `rest`, the text following an `&`, starts with a reference such as `amp;`, `#38;` or `#x26;`.
+fn starts_with_reference(rest: &str) -> bool {
+    let Some(end) = rest.find(';') else {
+        return false;
+    };
+    let name = &rest[..end];
+    if let Some(hex) = name.strip_prefix("#x") {
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(dec) = name.strip_prefix('#') {
+        !dec.is_empty() && dec.chars().all(|c| c.is_ascii_digit())
+    } else {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,4 +326,74 @@ mod tests {
         let content = FilingContentValue::new(first_entry_content);
         assert!(content.is_ok())
     }
+    #[test]
+    fn filing_content_value_hrefs() {
+        let feed = include_str!("../../tests/fixtures/browse_edgar_831001_10q.xml")
+            .parse::<Feed>()
+            .unwrap();
+        let first_entry_content = feed.entries.first().unwrap().content.clone().unwrap();
+        let content = FilingContentValue::new(first_entry_content).unwrap();
+        assert_eq!(
+            content.filing_href.value.as_str(),
+            "https://www.sec.gov/Archives/edgar/data/831001/000083100123000114/0000831001-23-000114-index.htm"
+        );
+        let file_number_href = content.file_number_href.unwrap().value;
+        assert!(file_number_href
+            .query_pairs()
+            .any(|(k, v)| k == "filenum" && v == "001-09924"));
+        let xbrl_href = content.xbrl_href.unwrap().value;
+        assert!(xbrl_href
+            .query_pairs()
+            .any(|(k, v)| k == "accession_number" && v == "0000831001-23-000114"));
+    }
+    #[test]
+    fn filing_content_value_typed_fields() {
+        let feed = include_str!("../../tests/fixtures/browse_edgar_831001_10q.xml")
+            .parse::<Feed>()
+            .unwrap();
+        let first_entry_content = feed.entries.first().unwrap().content.clone().unwrap();
+        let content = FilingContentValue::new(first_entry_content).unwrap();
+        assert_eq!(content.accession_number.value, "0000831001-23-000114");
+        assert_eq!(content.accession_number.sequence, 114);
+        assert_eq!(content.act.act, ActOption::ExchangeAct1934);
+        assert_eq!(
+            content.filing_date.date,
+            NaiveDate::from_ymd_opt(2023, 8, 4).unwrap()
+        );
+        assert_eq!(
+            content.filing_type.filing_type,
+            Some(FilingTypeOption::_10Q)
+        );
+        assert_eq!(content.size.value, "45 MB");
+        assert_eq!(content.size.bytes, 45 * 1024 * 1024)
+    }
+    #[test]
+    fn filing_content_value_invalid_accession_number() {
+        for invalid in [
+            "",
+            "0000831001-23",
+            "831001-23-000114",
+            "0000831001-2x-000114",
+        ] {
+            assert!(invalid.parse::<AccessionNumber>().is_err(), "{invalid}")
+        }
+    }
+    #[test]
+    fn filing_content_value_size() {
+        let size = |value: &str| {
+            Size::try_from(Text {
+                value: value.to_string(),
+            })
+        };
+        assert_eq!(size("7 KB").unwrap().bytes, 7 * 1024);
+        assert_eq!(size("1.5 MB").unwrap().bytes, 1536 * 1024);
+        assert!(size("big").is_err())
+    }
+    #[test]
+    fn filing_content_value_escape_bare_ampersands() {
+        assert_eq!(
+            escape_bare_ampersands("a?b=1&c=2&amp;d=&#38;&#x26;&"),
+            "a?b=1&amp;c=2&amp;d=&#38;&#x26;&amp;"
+        )
+    }
 }
@@ -36,6 +36,21 @@ pub enum EDGARError {
         #[from]
         source: serde_xml_rs::Error,
     },
+    #[error("Invalid Header Name")]
+    InvalidHeaderName {
+        #[from]
+        source: reqwest::header::InvalidHeaderName,
+    },
+    #[error("Invalid Header Value")]
+    InvalidHeaderValue {
+        #[from]
+        source: reqwest::header::InvalidHeaderValue,
+    },
+    #[error("Serde JSON Error")]
+    SerdeJSONError {
+        #[from]
+        source: serde_json::Error,
+    },
     #[error("CIK Not Found")]
     CIKNotFound,
     #[error("Filing Content Value Not Found")]
@@ -46,6 +61,34 @@ pub enum EDGARError {
     FilingContentValueNotFound,
     #[error("User Agent Env Var Missing")]
     UserAgentEnvVarMissing,
+    #[error("Invalid User Agent")]
+    InvalidUserAgent,
+    #[error("Invalid Rate Limit")]
+    InvalidRateLimit,
+    #[error("Request Rate Threshold Exceeded")]
+    RateThresholdExceeded,
+    #[error("Unexpected HTTP Status {status}")]
+    UnexpectedStatus { status: u16 },
+    #[error("Fixture Not Found For {url}")]
+    FixtureNotFound { url: String },
+    #[error("Invalid {field}: {value}")]
+    InvalidFieldValue { field: String, value: String },
+    #[error("Unsupported Compression Method {method}")]
+    UnsupportedCompression { method: u16 },
+    #[error("Expected {expected} Bytes But Downloaded {actual}")]
+    SizeMismatch { expected: u64, actual: u64 },
+    #[error("Expected SHA-256 {expected} But Downloaded {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+    #[error("Submission Header Not Found")]
+    SubmissionHeaderNotFound,
+    #[error("Ownership Document Not Found")]
+    OwnershipDocumentNotFound,
+    #[error("Form 13F Not Found")]
+    Form13FNotFound,
+    #[error("Form D Not Found")]
+    FormDNotFound,
+    #[error("Company Info Not Found")]
+    CompanyInfoNotFound,
     #[error("Getting Feed Failed")]
     GettingFeedFailed,
     #[error("Owner Option Not Found")]
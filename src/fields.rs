@@ -0,0 +1,92 @@
+//! This module holds the helpers that parse the fields of the documents filed on EDGAR.
+
+use crate::error::EDGARError;
+use chrono::NaiveDate;
+
+/// The trimmed `value`, or None if it is blank.
+pub(crate) fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+/// Flags are written as `1` or `0`, or `true` or `false`.
+pub(crate) fn flag(value: Option<String>) -> bool {
+    matches!(value.as_deref().map(str::trim), Some("1" | "true"))
+}
+/// Parses a date written as `2023-08-01`.
+pub(crate) fn date(value: &str) -> Result<NaiveDate, EDGARError> {
+    // Some filers append a time zone offset, such as `2023-08-01-05:00`.
+    let trimmed = value.trim();
+    let date = trimmed.get(..10).unwrap_or(trimmed);
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid_field("date", value))
+}
+/// Parses a whole number, which some filers write with thousands separators. None if it is blank.
+pub(crate) fn number(value: Option<&str>, field: &str) -> Result<Option<u64>, EDGARError> {
+    let Some(value) = value
+        .map(|v| v.trim().replace(',', ""))
+        .filter(|v| !v.is_empty())
+    else {
+        return Ok(None);
+    };
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid_field(field, &value))
+}
+/// Whether `xml` holds a `name` element, with or without a namespace prefix such as `ns1:`.
+pub(crate) fn has_element(xml: &str, name: &str) -> bool {
+    xml.contains(&format!("<{name}")) || xml.contains(&format!(":{name}"))
+}
+pub(crate) fn invalid_field(field: &str, value: &str) -> EDGARError {
+    EDGARError::InvalidFieldValue {
+        field: field.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_non_empty_and_flag() {
+        assert_eq!(
+            non_empty(Some(" CITIGROUP INC ".to_string())).as_deref(),
+            Some("CITIGROUP INC")
+        );
+        assert_eq!(non_empty(Some("  ".to_string())), None);
+        assert!(flag(Some("1".to_string())));
+        assert!(flag(Some(" true ".to_string())));
+        assert!(!flag(Some("0".to_string())));
+        assert!(!flag(None))
+    }
+    #[test]
+    fn fields_date() {
+        let date_ = NaiveDate::from_ymd_opt(2023, 8, 1).unwrap();
+        assert_eq!(date("2023-08-01").unwrap(), date_);
+        assert_eq!(date(" 2023-08-01-05:00").unwrap(), date_);
+        assert!(matches!(
+            date("08/01/2023"),
+            Err(EDGARError::InvalidFieldValue { .. })
+        ))
+    }
+    #[test]
+    fn fields_number() {
+        assert_eq!(number(Some(" 1,234,567 "), "value").unwrap(), Some(1234567));
+        assert_eq!(number(Some(""), "value").unwrap(), None);
+        assert_eq!(number(None, "value").unwrap(), None);
+        assert!(matches!(
+            number(Some("12.5"), "value"),
+            Err(EDGARError::InvalidFieldValue { .. })
+        ))
+    }
+    #[test]
+    fn fields_has_element() {
+        assert!(has_element(
+            "<edgarSubmission xmlns=\"\">",
+            "edgarSubmission"
+        ));
+        assert!(has_element("<ns1:informationTable>", "informationTable"));
+        assert!(!has_element("<informationTable>", "edgarSubmission"))
+    }
+}
@@ -0,0 +1,683 @@
+//! This module parses Form 13F, the quarterly report of the holdings of institutional investment managers.
+//!
+//! A 13F-HR has two XML documents:
+//! - the cover page, `primary_doc.xml`, see [CoverPage]
+//! - the information table, which lists the holdings, see [InformationTable]
+//!
+//! Filings made before January 3, 2023 report values in thousands of dollars, later filings in dollars, see [ValueUnits].
+//! Values are converted to dollars when parsed.
+//! ```
+//! use sec_edgar::{edgar::edgar_client, form_13f::get_form_13f};
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let accession_number = "0000950123-23-008074".parse().unwrap();
+//!     let form_13f = get_form_13f(client, "1067983", &accession_number)
+//!         .await
+//!         .unwrap();
+//!     for entry in form_13f.information_table.entries {
+//!         println!("{} {} ${}", entry.name_of_issuer, entry.cusip, entry.value);
+//!     }
+//! }
+//! ```
+pub mod holdings;
+
+use crate::archives::{complete_submission::get_complete_submission, filing_index::get_xml_files};
+use crate::client::EdgarClient;
+use crate::edgar_query::filing_content_value::AccessionNumber;
+use crate::error::EDGARError;
+use crate::fields::{has_element, invalid_field, non_empty, number};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_xml_rs::from_str;
+
+/// The units of the values of a 13F.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueUnits {
+    /// Thousands of dollars, for filings made before January 3, 2023.
+    Thousands,
+    /// Dollars, for filings made on or after January 3, 2023, including amendments of earlier reports.
+    Dollars,
+}
+impl ValueUnits {
+    /// The units of a 13F filed on `date`.
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use sec_edgar::form_13f::ValueUnits;
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2023, m, d).unwrap();
+    /// assert_eq!(ValueUnits::for_filing_date(date(1, 2)), ValueUnits::Thousands);
+    /// assert_eq!(ValueUnits::for_filing_date(date(1, 3)), ValueUnits::Dollars);
+    /// ```
+    pub fn for_filing_date(date: NaiveDate) -> Self {
+        match NaiveDate::from_ymd_opt(2023, 1, 3).is_some_and(|change| date < change) {
+            true => Self::Thousands,
+            false => Self::Dollars,
+        }
+    }
+    /// Converts `value`, in these units, to dollars.
+    ///
+    /// Returns [EDGARError::InvalidFieldValue] if the value in dollars does not fit in a [u64].
+    pub fn to_dollars(self, value: u64) -> Result<u64, EDGARError> {
+        match self {
+            Self::Thousands => value
+                .checked_mul(1000)
+                .ok_or_else(|| invalid_field("value", &value.to_string())),
+            Self::Dollars => Ok(value),
+        }
+    }
+}
+
+/// The kind of a 13F.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportType {
+    /// `13F HOLDINGS REPORT`: all the holdings of the manager are reported in the filing.
+    HoldingsReport,
+    /// `13F NOTICE`: all the holdings of the manager are reported by other managers.
+    Notice,
+    /// `13F COMBINATION REPORT`: some of the holdings of the manager are reported by other managers.
+    CombinationReport,
+    /// Any other report type, as written in the document.
+    Other(String),
+}
+impl From<&str> for ReportType {
+    fn from(report_type: &str) -> Self {
+        match report_type.trim() {
+            "13F HOLDINGS REPORT" => Self::HoldingsReport,
+            "13F NOTICE" => Self::Notice,
+            "13F COMBINATION REPORT" => Self::CombinationReport,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// The kind of an amendment.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AmendmentType {
+    /// `RESTATEMENT`: the amendment replaces the report.
+    Restatement,
+    /// `NEW HOLDINGS`: the amendment adds holdings to the report.
+    NewHoldings,
+    /// Any other amendment type, as written in the document.
+    Other(String),
+}
+impl From<&str> for AmendmentType {
+    fn from(amendment_type: &str) -> Self {
+        match amendment_type.trim() {
+            "RESTATEMENT" => Self::Restatement,
+            "NEW HOLDINGS" => Self::NewHoldings,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// Another manager named on the cover page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtherManager {
+    /// The number by which the information table refers to the manager, for included managers.
+    pub sequence_number: Option<u32>,
+    /// The CIK of the manager.
+    pub cik: Option<String>,
+    /// The 13F file number of the manager, such as `028-10388`.
+    pub file_number: Option<String>,
+    /// The name of the manager.
+    pub name: String,
+}
+
+/// The cover and summary pages of a 13F.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoverPage {
+    /// The last day of the quarter the report is for.
+    pub report_period: NaiveDate,
+    /// Whether the filing amends an earlier report.
+    pub is_amendment: bool,
+    /// The number of the amendment.
+    pub amendment_number: Option<u32>,
+    /// See [AmendmentType]
+    pub amendment_type: Option<AmendmentType>,
+    /// See [ReportType]
+    pub report_type: ReportType,
+    /// The name of the manager filing the report.
+    pub filing_manager: String,
+    /// The 13F file number of the filing manager.
+    pub file_number: Option<String>,
+    /// The managers that report holdings for the filing manager, for notices and combination reports.
+    pub other_managers_reporting: Vec<OtherManager>,
+    /// The other managers whose holdings are included in the report.
+    pub other_included_managers: Vec<OtherManager>,
+    /// The number of entries of the information table.
+    pub table_entry_total: Option<u64>,
+    /// The total value of the information table in dollars.
+    pub table_value_total: Option<u64>,
+}
+impl CoverPage {
+    /// Parses the XML of a cover page, whose values are in `units`.
+    pub fn new(xml: &str, units: ValueUnits) -> Result<Self, EDGARError> {
+        let raw: RawEdgarSubmission = from_str(xml.trim())?;
+        let cover = raw.form_data.cover_page;
+        let summary = raw.form_data.summary_page;
+        let report_period =
+            NaiveDate::parse_from_str(cover.report_calendar_or_quarter.trim(), "%m-%d-%Y")
+                .map_err(|_| invalid_field("report period", &cover.report_calendar_or_quarter))?;
+        let (table_entry_total, table_value_total) = match &summary {
+            Some(summary) => (
+                number(summary.table_entry_total.as_deref(), "table entry total")?,
+                number(summary.table_value_total.as_deref(), "table value total")?
+                    .map(|v| units.to_dollars(v))
+                    .transpose()?,
+            ),
+            None => (None, None),
+        };
+        Ok(Self {
+            report_period,
+            is_amendment: matches!(
+                cover.is_amendment.as_deref().map(str::trim),
+                Some("true" | "Y")
+            ),
+            amendment_number: number(cover.amendment_no.as_deref(), "amendment number")?
+                .map(|n| n as u32),
+            amendment_type: non_empty(cover.amendment_info.and_then(|a| a.amendment_type))
+                .map(|a| a.as_str().into()),
+            report_type: cover.report_type.as_str().into(),
+            filing_manager: cover.filing_manager.name.trim().to_string(),
+            file_number: non_empty(cover.form13_f_file_number),
+            other_managers_reporting: cover
+                .other_managers_info
+                .map(|o| o.other_manager)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|m| m.into_other_manager(None))
+                .collect(),
+            other_included_managers: summary
+                .and_then(|s| s.other_managers2_info)
+                .map(|o| o.other_manager2)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|m| {
+                    let sequence_number = m.sequence_number.trim().parse().ok();
+                    m.other_manager.into_other_manager(sequence_number)
+                })
+                .collect(),
+            table_entry_total,
+            table_value_total,
+        })
+    }
+}
+
+/// Whether the amount of a holding is a number of shares or a principal amount.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SharesOrPrincipal {
+    /// `SH`: a number of shares.
+    Shares,
+    /// `PRN`: a principal amount in dollars, such as for convertible debt.
+    Principal,
+}
+
+/// Whether a holding is an option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PutCall {
+    /// A put option.
+    Put,
+    /// A call option.
+    Call,
+}
+
+/// Who has the power to decide how a holding is invested.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InvestmentDiscretion {
+    /// `SOLE`: the filing manager only.
+    Sole,
+    /// `DFND`: shared with other managers, as defined in the instructions of the form.
+    Defined,
+    /// `OTR`: shared otherwise.
+    Other,
+}
+
+/// The number of shares of a holding for which the manager has each kind of voting authority.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VotingAuthority {
+    /// Sole voting authority.
+    pub sole: u64,
+    /// Shared voting authority.
+    pub shared: u64,
+    /// No voting authority.
+    pub none: u64,
+}
+
+/// A holding of a 13F.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InformationTableEntry {
+    /// The name of the issuer, such as `APPLE INC`.
+    pub name_of_issuer: String,
+    /// The title of the class of the security, such as `COM`.
+    pub title_of_class: String,
+    /// The CUSIP of the security.
+    pub cusip: String,
+    /// The FIGI of the security, which filers may leave out.
+    pub figi: Option<String>,
+    /// The market value of the holding in dollars.
+    pub value: u64,
+    /// The number of shares or the principal amount, see `shares_or_principal_type`.
+    pub shares_or_principal: u64,
+    /// See [SharesOrPrincipal]
+    pub shares_or_principal_type: SharesOrPrincipal,
+    /// See [PutCall]. None if the holding is not an option.
+    pub put_call: Option<PutCall>,
+    /// See [InvestmentDiscretion]
+    pub investment_discretion: InvestmentDiscretion,
+    /// The sequence numbers of the [other included managers](CoverPage::other_included_managers) the holding is reported for.
+    pub other_managers: Vec<u32>,
+    /// See [VotingAuthority]
+    pub voting_authority: VotingAuthority,
+}
+
+/// The information table of a 13F.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InformationTable {
+    /// See [InformationTableEntry]
+    pub entries: Vec<InformationTableEntry>,
+}
+impl InformationTable {
+    /// Parses the XML of an information table, whose values are in `units`.
+    pub fn new(xml: &str, units: ValueUnits) -> Result<Self, EDGARError> {
+        let raw: RawInformationTable = from_str(xml.trim())?;
+        let entries = raw
+            .info_table
+            .into_iter()
+            .map(|entry| entry.into_entry(units))
+            .collect::<Result<_, EDGARError>>()?;
+        Ok(Self { entries })
+    }
+    /// The total value of the entries in dollars. It is a [u128] because the sum of values that each fit in a [u64] may not.
+    pub fn total_value(&self) -> u128 {
+        self.entries.iter().map(|e| u128::from(e.value)).sum()
+    }
+}
+
+/// A 13F filing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Form13F {
+    /// The accession number of the filing.
+    pub accession_number: AccessionNumber,
+    /// The date the filing was made.
+    pub filed_as_of_date: Option<NaiveDate>,
+    /// The units the values were reported in, before being converted to dollars.
+    pub units: ValueUnits,
+    /// See [CoverPage]
+    pub cover_page: CoverPage,
+    /// See [InformationTable]. Empty for a notice.
+    pub information_table: InformationTable,
+}
+
+/// Get the 13F filing `accession_number` of the manager with `cik`.
+///
+/// The cover page and information table are read from the XML documents listed by the `-index.htm` of the filing,
+/// and the filing date from its header. Older filings without them are read from the complete submission text file.
+pub async fn get_form_13f(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+) -> Result<Form13F, EDGARError> {
+    let files = get_xml_files(client.clone(), cik, accession_number, |d| {
+        d.file_name == "primary_doc.xml"
+            || d.document_type
+                .as_deref()
+                .is_some_and(|t| t.eq_ignore_ascii_case("INFORMATION TABLE"))
+    })
+    .await?;
+    let (filed_as_of_date, documents) = match files {
+        Some(files)
+            if files.filing_date.is_some()
+                && files
+                    .texts
+                    .iter()
+                    .any(|t| has_element(t, "edgarSubmission")) =>
+        {
+            (files.filing_date, files.texts)
+        }
+        _ => {
+            let mut reader = get_complete_submission(client, cik, accession_number).await?;
+            let mut documents = vec![];
+            while let Some(document) = reader.next_document().await? {
+                documents.push(document.text());
+            }
+            (reader.header().filed_as_of_date, documents)
+        }
+    };
+    let units = filed_as_of_date.map_or(ValueUnits::Dollars, ValueUnits::for_filing_date);
+    let mut cover_page = None;
+    let mut information_table = InformationTable::default();
+    for text in documents {
+        if has_element(&text, "edgarSubmission") {
+            cover_page = Some(CoverPage::new(&text, units)?);
+        } else if has_element(&text, "informationTable") {
+            information_table = InformationTable::new(&text, units)?;
+        }
+    }
+    Ok(Form13F {
+        accession_number: accession_number.clone(),
+        filed_as_of_date,
+        units,
+        cover_page: cover_page.ok_or(EDGARError::Form13FNotFound)?,
+        information_table,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEdgarSubmission {
+    form_data: RawFormData,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFormData {
+    cover_page: RawCoverPage,
+    summary_page: Option<RawSummaryPage>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCoverPage {
+    report_calendar_or_quarter: String,
+    is_amendment: Option<String>,
+    amendment_no: Option<String>,
+    amendment_info: Option<RawAmendmentInfo>,
+    filing_manager: RawFilingManager,
+    #[serde(default)]
+    report_type: String,
+    #[serde(rename = "form13FFileNumber")]
+    form13_f_file_number: Option<String>,
+    other_managers_info: Option<RawOtherManagersInfo>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAmendmentInfo {
+    amendment_type: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFilingManager {
+    name: String,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOtherManagersInfo {
+    #[serde(default)]
+    other_manager: Vec<RawOtherManager>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOtherManager {
+    cik: Option<String>,
+    #[serde(rename = "form13FFileNumber")]
+    form13_f_file_number: Option<String>,
+    #[serde(default)]
+    name: String,
+}
+impl RawOtherManager {
+    fn into_other_manager(self, sequence_number: Option<u32>) -> OtherManager {
+        OtherManager {
+            sequence_number,
+            cik: non_empty(self.cik),
+            file_number: non_empty(self.form13_f_file_number),
+            name: self.name.trim().to_string(),
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSummaryPage {
+    table_entry_total: Option<String>,
+    table_value_total: Option<String>,
+    other_managers2_info: Option<RawOtherManagers2Info>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOtherManagers2Info {
+    #[serde(default)]
+    other_manager2: Vec<RawOtherManager2>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOtherManager2 {
+    #[serde(default)]
+    sequence_number: String,
+    other_manager: RawOtherManager,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInformationTable {
+    #[serde(default)]
+    info_table: Vec<RawInfoTable>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInfoTable {
+    name_of_issuer: String,
+    title_of_class: String,
+    cusip: String,
+    figi: Option<String>,
+    value: String,
+    shrs_or_prn_amt: RawShrsOrPrnAmt,
+    put_call: Option<String>,
+    investment_discretion: String,
+    other_manager: Option<String>,
+    voting_authority: RawVotingAuthority,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawShrsOrPrnAmt {
+    ssh_prnamt: String,
+    ssh_prnamt_type: String,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawVotingAuthority {
+    sole: String,
+    shared: String,
+    none: String,
+}
+impl RawInfoTable {
+    fn into_entry(self, units: ValueUnits) -> Result<InformationTableEntry, EDGARError> {
+        let required = |value: &str, field: &str| {
+            number(Some(value), field)?.ok_or_else(|| invalid_field(field, value))
+        };
+        let shares_or_principal_type = match self.shrs_or_prn_amt.ssh_prnamt_type.trim() {
+            "SH" => SharesOrPrincipal::Shares,
+            "PRN" => SharesOrPrincipal::Principal,
+            other => return Err(invalid_field("shares or principal type", other)),
+        };
+        let put_call = match non_empty(self.put_call).as_deref() {
+            None => None,
+            Some(p) if p.eq_ignore_ascii_case("put") => Some(PutCall::Put),
+            Some(p) if p.eq_ignore_ascii_case("call") => Some(PutCall::Call),
+            Some(other) => return Err(invalid_field("put or call", other)),
+        };
+        let investment_discretion = match self.investment_discretion.trim() {
+            "SOLE" => InvestmentDiscretion::Sole,
+            "DFND" => InvestmentDiscretion::Defined,
+            "OTR" => InvestmentDiscretion::Other,
+            other => return Err(invalid_field("investment discretion", other)),
+        };
+        // Filers separate the sequence numbers with commas, spaces or both.
+        let other_managers = self
+            .other_manager
+            .unwrap_or_default()
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|n| n.parse().ok())
+            .collect();
+        Ok(InformationTableEntry {
+            name_of_issuer: self.name_of_issuer.trim().to_string(),
+            title_of_class: self.title_of_class.trim().to_string(),
+            cusip: self.cusip.trim().to_uppercase(),
+            figi: non_empty(self.figi),
+            value: units.to_dollars(required(&self.value, "value")?)?,
+            shares_or_principal: required(&self.shrs_or_prn_amt.ssh_prnamt, "shares or principal")?,
+            shares_or_principal_type,
+            put_call,
+            investment_discretion,
+            other_managers,
+            voting_authority: VotingAuthority {
+                sole: required(&self.voting_authority.sole, "sole voting authority")?,
+                shared: required(&self.voting_authority.shared, "shared voting authority")?,
+                none: required(&self.voting_authority.none, "no voting authority")?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{
+        client, filing_index, xml_submission, TestResponse, TestServer,
+    };
+
+    const COVER_PAGE: &str = include_str!("../tests/fixtures/form_13f/primary_doc.xml");
+    const INFORMATION_TABLE: &str = include_str!("../tests/fixtures/form_13f/infotable.xml");
+
+    #[test]
+    fn form_13f_cover_page() {
+        let cover = CoverPage::new(COVER_PAGE, ValueUnits::Dollars).unwrap();
+        assert_eq!(
+            cover.report_period,
+            NaiveDate::from_ymd_opt(2023, 6, 30).unwrap()
+        );
+        assert!(!cover.is_amendment);
+        assert_eq!(cover.amendment_type, None);
+        assert_eq!(cover.report_type, ReportType::HoldingsReport);
+        assert_eq!(cover.filing_manager, "Berkshire Hathaway Inc");
+        assert_eq!(cover.file_number.as_deref(), Some("028-04545"));
+        assert!(cover.other_managers_reporting.is_empty());
+        assert_eq!(
+            cover.other_included_managers[1],
+            OtherManager {
+                sequence_number: Some(2),
+                cik: None,
+                file_number: Some("028-05194".to_string()),
+                name: "National Indemnity Company".to_string(),
+            }
+        );
+        assert_eq!(cover.table_entry_total, Some(3));
+        assert_eq!(cover.table_value_total, Some(208519733861))
+    }
+    #[test]
+    fn form_13f_amendment() {
+        let xml = COVER_PAGE.replace(
+            "<isAmendment>false</isAmendment>",
+            "<isAmendment>true</isAmendment>\
+             <amendmentNo>1</amendmentNo>\
+             <amendmentInfo><amendmentType>NEW HOLDINGS</amendmentType></amendmentInfo>",
+        );
+        let cover = CoverPage::new(&xml, ValueUnits::Thousands).unwrap();
+        assert!(cover.is_amendment);
+        assert_eq!(cover.amendment_number, Some(1));
+        assert_eq!(cover.amendment_type, Some(AmendmentType::NewHoldings));
+        assert_eq!(cover.table_value_total, Some(208519733861000))
+    }
+    #[test]
+    fn form_13f_information_table() {
+        let table = InformationTable::new(INFORMATION_TABLE, ValueUnits::Dollars).unwrap();
+        assert_eq!(table.entries.len(), 3);
+        assert_eq!(table.total_value(), 208519733861);
+        let largest = InformationTableEntry {
+            value: u64::MAX,
+            ..table.entries[0].clone()
+        };
+        let huge = InformationTable {
+            entries: vec![largest; 2],
+        };
+        assert_eq!(huge.total_value(), 2 * u128::from(u64::MAX));
+        let apple = &table.entries[0];
+        assert_eq!(apple.cusip, "037833100");
+        assert_eq!(apple.figi.as_deref(), Some("BBG000B9XRY4"));
+        assert_eq!(apple.shares_or_principal, 915560382);
+        assert_eq!(apple.shares_or_principal_type, SharesOrPrincipal::Shares);
+        assert_eq!(apple.put_call, None);
+        assert_eq!(apple.investment_discretion, InvestmentDiscretion::Defined);
+        assert_eq!(apple.other_managers, vec![1, 2]);
+        assert_eq!(apple.voting_authority.sole, 915560382);
+        let spy = &table.entries[2];
+        assert_eq!(spy.name_of_issuer, "SPDR S&P 500 ETF TR");
+        assert_eq!(spy.put_call, Some(PutCall::Put));
+        assert!(spy.other_managers.is_empty());
+        assert_eq!(spy.voting_authority.none, 1585000);
+        // The same table filed in 2022 would be in thousands.
+        let table = InformationTable::new(INFORMATION_TABLE, ValueUnits::Thousands).unwrap();
+        assert_eq!(table.entries[2].value, 702636000000)
+    }
+    #[test]
+    fn form_13f_value_overflow() {
+        assert_eq!(ValueUnits::Thousands.to_dollars(5).unwrap(), 5000);
+        assert_eq!(ValueUnits::Dollars.to_dollars(u64::MAX).unwrap(), u64::MAX);
+        let xml = INFORMATION_TABLE.replace(
+            "<value>177591247357</value>",
+            &format!("<value>{}</value>", u64::MAX / 10),
+        );
+        assert!(InformationTable::new(&xml, ValueUnits::Dollars).is_ok());
+        assert!(matches!(
+            InformationTable::new(&xml, ValueUnits::Thousands),
+            Err(EDGARError::InvalidFieldValue { .. })
+        ))
+    }
+    #[tokio::test]
+    async fn form_13f_get_form_13f() {
+        let folder = "/Archives/edgar/data/1067983/000095012323008074";
+        let index = filing_index(
+            "2023-08-14",
+            &[
+                ("13F-HR", "primary_doc.html"),
+                ("13F-HR", "primary_doc.xml"),
+                ("INFORMATION TABLE", "46994.html"),
+                ("INFORMATION TABLE", "46994.xml"),
+            ],
+        );
+        let server = TestServer::start()
+            .route(
+                &format!("{folder}/0000950123-23-008074-index.htm"),
+                vec![TestResponse::ok("text/html", &index)],
+            )
+            .route(
+                &format!("{folder}/primary_doc.xml"),
+                vec![TestResponse::ok("text/xml", COVER_PAGE.as_bytes())],
+            )
+            .route(
+                &format!("{folder}/46994.xml"),
+                vec![TestResponse::ok("text/xml", INFORMATION_TABLE.as_bytes())],
+            );
+        let accession_number = "0000950123-23-008074".parse().unwrap();
+        let form_13f = get_form_13f(client(&server), "0001067983", &accession_number)
+            .await
+            .unwrap();
+        assert_eq!(form_13f.units, ValueUnits::Dollars);
+        assert_eq!(
+            form_13f.filed_as_of_date,
+            NaiveDate::from_ymd_opt(2023, 8, 14)
+        );
+        assert_eq!(form_13f.cover_page.other_included_managers.len(), 2);
+        assert_eq!(
+            form_13f.information_table.total_value(),
+            u128::from(form_13f.cover_page.table_value_total.unwrap())
+        );
+        // The complete submission is not downloaded when the folder has the documents.
+        assert!(server
+            .requests()
+            .iter()
+            .all(|request| !request[0].contains(".txt")));
+        // Older filings have no -index.htm and are read from the complete submission.
+        let submission = xml_submission(
+            "0000950123-23-008074",
+            "13F-HR",
+            "20230814",
+            &[
+                ("13F-HR", COVER_PAGE),
+                ("INFORMATION TABLE", INFORMATION_TABLE),
+            ],
+        );
+        let server = TestServer::start().route(
+            &format!("{folder}/0000950123-23-008074.txt"),
+            vec![TestResponse::ok("text/plain", &submission)],
+        );
+        let from_submission = get_form_13f(client(&server), "1067983", &accession_number)
+            .await
+            .unwrap();
+        assert_eq!(from_submission, form_13f)
+    }
+}
@@ -0,0 +1,469 @@
+//! This module puts together the holdings a manager reported for a quarter, and compares them with the quarter before.
+//!
+//! The holdings of a quarter are those of its 13F-HR, with each 13F-HR/A applied in the order they were filed:
+//! a restatement replaces the holdings, and an amendment of new holdings adds to them.
+//! ```
+//! use sec_edgar::{
+//!     edgar::edgar_client,
+//!     form_13f::holdings::{get_holdings_diff, PositionChange},
+//! };
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     // The latest quarter reported, compared with the quarter before.
+//!     let diff = get_holdings_diff(client, "1067983", None).await.unwrap();
+//!     for position in diff.with_change(PositionChange::New) {
+//!         println!("{} {} ${}", position.name_of_issuer, position.cusip, position.current_value);
+//!     }
+//! }
+//! ```
+
+use crate::client::EdgarClient;
+use crate::edgar_query::filing_content_value::AccessionNumber;
+use crate::error::EDGARError;
+use crate::form_13f::{get_form_13f, AmendmentType, InformationTable, PutCall};
+use crate::submissions::{get_submissions, SubmissionsFiling};
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+
+/// The holdings a manager reported for a quarter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holdings {
+    /// The last day of the quarter.
+    pub report_period: NaiveDate,
+    /// The 13F-HR and the amendments applied to it, in the order they were filed.
+    pub filings: Vec<AccessionNumber>,
+    /// The entries of the information tables, with values in dollars.
+    pub information_table: InformationTable,
+}
+
+/// Get the holdings the manager with `cik` reported for the quarter ending on `report_period`, amendments included.
+pub async fn get_holdings(
+    client: EdgarClient,
+    cik: &str,
+    report_period: NaiveDate,
+) -> Result<Holdings, EDGARError> {
+    let submissions = get_submissions(client.clone(), cik).await?;
+    holdings(client, cik, &submissions.filings, report_period).await
+}
+
+/// How a position changed from one quarter to the next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PositionChange {
+    /// Held in the current quarter only.
+    New,
+    /// Held in the previous quarter only.
+    Closed,
+    /// Held with more shares or principal than in the previous quarter.
+    Increased,
+    /// Held with fewer shares or principal than in the previous quarter.
+    Decreased,
+    /// Held with the same shares or principal as in the previous quarter.
+    Unchanged,
+}
+
+/// A position compared between two quarters.
+///
+/// A position is a CUSIP, and the put or call for options, so that the options on a security are not mixed with the security.
+/// The entries of a quarter with the same position, such as those reported for different managers, are added up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionDiff {
+    /// The CUSIP of the security.
+    pub cusip: String,
+    /// The name of the issuer, as reported in the latest quarter the position was held.
+    pub name_of_issuer: String,
+    /// The title of the class of the security, as reported in the latest quarter the position was held.
+    pub title_of_class: String,
+    /// See [PutCall]
+    pub put_call: Option<PutCall>,
+    /// See [PositionChange]
+    pub change: PositionChange,
+    /// The shares or principal amount in the previous quarter. Sums too large for a [u64] saturate, as do the other amounts.
+    pub previous_shares: u64,
+    /// The shares or principal amount in the current quarter.
+    pub current_shares: u64,
+    /// The value in dollars in the previous quarter.
+    pub previous_value: u64,
+    /// The value in dollars in the current quarter.
+    pub current_value: u64,
+}
+impl PositionDiff {
+    /// The change in shares or principal amount.
+    pub fn share_change(&self) -> i128 {
+        i128::from(self.current_shares) - i128::from(self.previous_shares)
+    }
+    /// The change in value in dollars, which includes the change in price.
+    pub fn value_change(&self) -> i128 {
+        i128::from(self.current_value) - i128::from(self.previous_value)
+    }
+}
+
+/// The positions of a manager compared between two quarters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HoldingsDiff {
+    /// The last day of the previous quarter.
+    pub previous_period: NaiveDate,
+    /// The last day of the current quarter.
+    pub current_period: NaiveDate,
+    /// See [PositionDiff]. Ordered by the size of the change in value, largest first.
+    pub positions: Vec<PositionDiff>,
+}
+impl HoldingsDiff {
+    /// Compares the `current` holdings with the `previous` ones.
+    pub fn new(previous: &Holdings, current: &Holdings) -> Self {
+        let mut positions: BTreeMap<(String, Option<PutCall>), PositionDiff> = BTreeMap::new();
+        for (holdings, is_current) in [(previous, false), (current, true)] {
+            for entry in &holdings.information_table.entries {
+                let key = (entry.cusip.clone(), entry.put_call);
+                let position = positions.entry(key).or_insert_with(|| PositionDiff {
+                    cusip: entry.cusip.clone(),
+                    name_of_issuer: String::new(),
+                    title_of_class: String::new(),
+                    put_call: entry.put_call,
+                    change: PositionChange::Unchanged,
+                    previous_shares: 0,
+                    current_shares: 0,
+                    previous_value: 0,
+                    current_value: 0,
+                });
+                position.name_of_issuer = entry.name_of_issuer.clone();
+                position.title_of_class = entry.title_of_class.clone();
+                let (shares, value) = match is_current {
+                    true => (&mut position.current_shares, &mut position.current_value),
+                    false => (&mut position.previous_shares, &mut position.previous_value),
+                };
+                *shares = shares.saturating_add(entry.shares_or_principal);
+                *value = value.saturating_add(entry.value);
+            }
+        }
+        let mut positions: Vec<PositionDiff> = positions.into_values().collect();
+        for position in &mut positions {
+            let held = |shares: u64, value: u64| shares > 0 || value > 0;
+            position.change = match (
+                held(position.previous_shares, position.previous_value),
+                held(position.current_shares, position.current_value),
+            ) {
+                (false, true) => PositionChange::New,
+                (true, false) => PositionChange::Closed,
+                _ => match position.current_shares.cmp(&position.previous_shares) {
+                    std::cmp::Ordering::Greater => PositionChange::Increased,
+                    std::cmp::Ordering::Less => PositionChange::Decreased,
+                    std::cmp::Ordering::Equal => PositionChange::Unchanged,
+                },
+            };
+        }
+        positions.sort_by_key(|p| std::cmp::Reverse(p.value_change().unsigned_abs()));
+        Self {
+            previous_period: previous.report_period,
+            current_period: current.report_period,
+            positions,
+        }
+    }
+    /// The positions with `change`.
+    pub fn with_change(&self, change: PositionChange) -> impl Iterator<Item = &PositionDiff> {
+        self.positions.iter().filter(move |p| p.change == change)
+    }
+}
+
+/// Get the holdings the manager with `cik` reported for the quarter ending on `report_period`, compared with the quarter before.
+/// If `report_period` is None, the latest quarter the manager reported is used.
+pub async fn get_holdings_diff(
+    client: EdgarClient,
+    cik: &str,
+    report_period: Option<NaiveDate>,
+) -> Result<HoldingsDiff, EDGARError> {
+    let submissions = get_submissions(client.clone(), cik).await?;
+    let report_period = match report_period {
+        Some(report_period) => report_period,
+        None => submissions
+            .filings
+            .iter()
+            .filter(|f| is_13f_hr(f))
+            .filter_map(|f| f.report_date)
+            .max()
+            .ok_or(EDGARError::Form13FNotFound)?,
+    };
+    let previous_period = previous_quarter_end(report_period);
+    let current = holdings(client.clone(), cik, &submissions.filings, report_period).await?;
+    let previous = holdings(client, cik, &submissions.filings, previous_period).await?;
+    Ok(HoldingsDiff::new(&previous, &current))
+}
+
+/// Applies the 13F-HR and 13F-HR/A of `filings` for `report_period` in the order they were filed.
+async fn holdings(
+    client: EdgarClient,
+    cik: &str,
+    filings: &[SubmissionsFiling],
+    report_period: NaiveDate,
+) -> Result<Holdings, EDGARError> {
+    let mut filings: Vec<&SubmissionsFiling> = filings
+        .iter()
+        .filter(|f| is_13f_hr(f) && f.report_date == Some(report_period))
+        .collect();
+    if filings.is_empty() {
+        return Err(EDGARError::Form13FNotFound);
+    }
+    filings.sort_by_key(|f| (f.filing_date, f.acceptance_date_time));
+    let mut holdings = Holdings {
+        report_period,
+        filings: vec![],
+        information_table: InformationTable::default(),
+    };
+    for filing in filings {
+        let form_13f = get_form_13f(client.clone(), cik, &filing.accession_number).await?;
+        let entries = form_13f.information_table.entries;
+        match (
+            form_13f.cover_page.is_amendment,
+            form_13f.cover_page.amendment_type,
+        ) {
+            (false, _) | (true, Some(AmendmentType::Restatement)) => {
+                holdings.information_table.entries = entries
+            }
+            (true, Some(AmendmentType::NewHoldings)) => {
+                holdings.information_table.entries.extend(entries)
+            }
+            // Other amendments, such as those for confidential treatment, do not change the holdings.
+            _ => continue,
+        }
+        holdings.filings.push(form_13f.accession_number);
+    }
+    Ok(holdings)
+}
+fn is_13f_hr(filing: &SubmissionsFiling) -> bool {
+    matches!(filing.form.as_str(), "13F-HR" | "13F-HR/A")
+}
+/// The last day of the quarter before the one `date` is in.
+fn previous_quarter_end(date: NaiveDate) -> NaiveDate {
+    let quarter_start = NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1)
+        .expect("the first day of a quarter is a valid date");
+    quarter_start.pred_opt().unwrap_or(quarter_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, xml_submission, TestResponse, TestServer};
+    use crate::form_13f::ValueUnits;
+
+    const COVER_PAGE: &str = include_str!("../../tests/fixtures/form_13f/primary_doc.xml");
+    const INFORMATION_TABLE: &str = include_str!("../../tests/fixtures/form_13f/infotable.xml");
+
+    /// An information table with one entry for each `(name, cusip, shares, value)`.
+    fn information_table(entries: &[(&str, &str, u64, u64)]) -> String {
+        let mut xml = "<informationTable>".to_string();
+        for (name, cusip, shares, value) in entries {
+            xml += &format!(
+                "<infoTable><nameOfIssuer>{name}</nameOfIssuer><titleOfClass>COM</titleOfClass>\
+                 <cusip>{cusip}</cusip><value>{value}</value>\
+                 <shrsOrPrnAmt><sshPrnamt>{shares}</sshPrnamt><sshPrnamtType>SH</sshPrnamtType></shrsOrPrnAmt>\
+                 <investmentDiscretion>SOLE</investmentDiscretion>\
+                 <votingAuthority><Sole>{shares}</Sole><Shared>0</Shared><None>0</None></votingAuthority></infoTable>"
+            );
+        }
+        xml + "</informationTable>"
+    }
+    fn cover_page(period: &str, amendment_type: Option<&str>) -> String {
+        let cover = COVER_PAGE.replace("06-30-2023", period);
+        match amendment_type {
+            Some(amendment_type) => cover.replace(
+                "<isAmendment>false</isAmendment>",
+                &format!(
+                    "<isAmendment>true</isAmendment>\
+                     <amendmentInfo><amendmentType>{amendment_type}</amendmentType></amendmentInfo>"
+                ),
+            ),
+            None => cover,
+        }
+    }
+    fn route(
+        server: TestServer,
+        accession_number: &str,
+        filed: &str,
+        cover_page: &str,
+        information_table: &str,
+    ) -> TestServer {
+        let submission = xml_submission(
+            accession_number,
+            "13F-HR",
+            filed,
+            &[
+                ("13F-HR", cover_page),
+                ("INFORMATION TABLE", information_table),
+            ],
+        );
+        server.route(
+            &format!(
+                "/Archives/edgar/data/1067983/{}/{accession_number}.txt",
+                accession_number.replace('-', "")
+            ),
+            vec![TestResponse::ok("text/plain", &submission)],
+        )
+    }
+    fn server() -> TestServer {
+        let server = TestServer::start().route(
+            "/submissions/CIK0001067983.json",
+            vec![TestResponse::ok(
+                "application/json",
+                include_bytes!("../../tests/fixtures/form_13f/CIK0001067983.json"),
+            )],
+        );
+        let first_quarter = information_table(&[
+            ("APPLE INC", "037833100", 895136175, 151846958720),
+            ("BANK AMER CORP", "060505104", 1032852006, 29539567372),
+            ("CHEVRON CORP NEW", "166764100", 132248576, 21577678664),
+            ("ACTIVISION BLIZZARD INC", "00507V109", 49400000, 4228146000),
+        ]);
+        let server = route(
+            server,
+            "0000950123-23-005270",
+            "20230515",
+            &cover_page("03-31-2023", None),
+            &information_table(&[("APPLE INC", "037833100", 1, 1)]),
+        );
+        let server = route(
+            server,
+            "0000950123-23-005412",
+            "20230519",
+            &cover_page("03-31-2023", Some("RESTATEMENT")),
+            &first_quarter,
+        );
+        let server = route(
+            server,
+            "0000950123-23-008074",
+            "20230814",
+            &cover_page("06-30-2023", None),
+            INFORMATION_TABLE,
+        );
+        route(
+            server,
+            "0000950123-23-008350",
+            "20230821",
+            &cover_page("06-30-2023", Some("NEW HOLDINGS")),
+            &information_table(&[("CHEVRON CORP NEW", "166764100", 110248289, 17347580274)]),
+        )
+    }
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+    #[test]
+    fn holdings_previous_quarter_end() {
+        assert_eq!(previous_quarter_end(date(2023, 6, 30)), date(2023, 3, 31));
+        assert_eq!(previous_quarter_end(date(2023, 3, 31)), date(2022, 12, 31));
+        assert_eq!(previous_quarter_end(date(2023, 12, 31)), date(2023, 9, 30))
+    }
+    #[test]
+    fn holdings_diff_adds_up_entries_and_separates_options() {
+        let holdings = |period, xml: &str, units| Holdings {
+            report_period: period,
+            filings: vec![],
+            information_table: InformationTable::new(xml, units).unwrap(),
+        };
+        // Values in thousands before 2023 and in dollars after.
+        let previous = holdings(
+            date(2022, 12, 31),
+            &information_table(&[
+                ("APPLE INC", "037833100", 500, 60),
+                ("APPLE INC", "037833100", 500, 60),
+            ]),
+            ValueUnits::Thousands,
+        );
+        let current = holdings(
+            date(2023, 3, 31),
+            &INFORMATION_TABLE.replace("78462F103", "037833100"),
+            ValueUnits::Dollars,
+        );
+        let diff = HoldingsDiff::new(&previous, &current);
+        assert_eq!(diff.positions.len(), 3);
+        let apple = diff
+            .positions
+            .iter()
+            .find(|p| p.cusip == "037833100" && p.put_call.is_none())
+            .unwrap();
+        assert_eq!(apple.previous_shares, 1000);
+        assert_eq!(apple.previous_value, 120000);
+        assert_eq!(apple.change, PositionChange::Increased);
+        let put = diff
+            .with_change(PositionChange::New)
+            .find(|p| p.put_call == Some(PutCall::Put))
+            .unwrap();
+        assert_eq!(put.cusip, "037833100");
+        assert_eq!(put.share_change(), 1585000)
+    }
+    #[test]
+    fn holdings_diff_large_amounts() {
+        let holdings = |period, entries: &[(&str, &str, u64, u64)]| Holdings {
+            report_period: period,
+            filings: vec![],
+            information_table: InformationTable::new(
+                &information_table(entries),
+                ValueUnits::Dollars,
+            )
+            .unwrap(),
+        };
+        let max = u64::MAX;
+        let previous = holdings(
+            date(2023, 3, 31),
+            &[
+                ("APPLE INC", "037833100", max, max),
+                ("CHEVRON CORP NEW", "166764100", max, max),
+            ],
+        );
+        let current = holdings(
+            date(2023, 6, 30),
+            &[
+                ("APPLE INC", "037833100", max, max),
+                ("APPLE INC", "037833100", 1, 1),
+            ],
+        );
+        let diff = HoldingsDiff::new(&previous, &current);
+        let apple = diff.with_change(PositionChange::Unchanged).next().unwrap();
+        assert_eq!(apple.current_shares, max);
+        assert_eq!(apple.value_change(), 0);
+        let chevron = diff.with_change(PositionChange::Closed).next().unwrap();
+        assert_eq!(chevron.share_change(), -i128::from(max));
+        assert_eq!(chevron.value_change(), -i128::from(max))
+    }
+    #[tokio::test]
+    async fn holdings_get_holdings_applies_amendments() {
+        let server = server();
+        let holdings = get_holdings(client(&server), "1067983", date(2023, 3, 31))
+            .await
+            .unwrap();
+        assert_eq!(holdings.filings.len(), 2);
+        assert_eq!(holdings.information_table.entries.len(), 4);
+        let missing = get_holdings(client(&server), "1067983", date(2022, 9, 30)).await;
+        assert!(matches!(missing, Err(EDGARError::Form13FNotFound)))
+    }
+    #[tokio::test]
+    async fn holdings_get_holdings_diff() {
+        let server = server();
+        let diff = get_holdings_diff(client(&server), "1067983", None)
+            .await
+            .unwrap();
+        assert_eq!(diff.previous_period, date(2023, 3, 31));
+        assert_eq!(diff.current_period, date(2023, 6, 30));
+        let changes: Vec<(&str, PositionChange)> = diff
+            .positions
+            .iter()
+            .map(|p| (p.cusip.as_str(), p.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("037833100", PositionChange::Increased),
+                ("166764100", PositionChange::Decreased),
+                ("00507V109", PositionChange::Closed),
+                ("78462F103", PositionChange::New),
+                ("060505104", PositionChange::Unchanged),
+            ]
+        );
+        let apple = &diff.positions[0];
+        assert_eq!(apple.share_change(), 20424207);
+        assert_eq!(apple.value_change(), 25744288637);
+        let chevron = &diff.positions[1];
+        assert_eq!(chevron.share_change(), -22000287);
+        assert_eq!(chevron.current_value, 17347580274);
+        let closed: Vec<_> = diff.with_change(PositionChange::Closed).collect();
+        assert_eq!(closed[0].current_shares, 0);
+        assert_eq!(closed[0].value_change(), -4228146000)
+    }
+}
@@ -0,0 +1,1125 @@
+//! This module parses Form D, the notice of an offering of securities sold without registration under Regulation D or Section 4(a)(5).
+//!
+//! The XML of a Form D is the `primary_doc.xml` of the filing, see [FormD].
+//! ```
+//! use sec_edgar::{edgar::edgar_client, form_d::{get_form_d, OfferingAmount}};
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let accession_number = "0001987654-23-000001".parse().unwrap();
+//!     let form_d = get_form_d(client, "1987654", &accession_number)
+//!         .await
+//!         .unwrap();
+//!     let amounts = &form_d.offering_amounts;
+//!     if let OfferingAmount::Dollars(total) = amounts.total_offering_amount {
+//!         println!("{} raised ${} of ${}", form_d.primary_issuer.name, amounts.total_amount_sold, total);
+//!     }
+//! }
+//! ```
+
+use crate::archives::{complete_submission::get_complete_submission, filing_index::get_xml_files};
+use crate::client::EdgarClient;
+use crate::edgar_query::{
+    company_info::{Address, AddressType},
+    edgar_query_builder::add_leading_zeros_to_cik,
+    filing_content_value::AccessionNumber,
+};
+use crate::error::EDGARError;
+use crate::fields::{date, flag, has_element, invalid_field, non_empty, number};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_xml_rs::from_str;
+
+/// When the issuer was incorporated or organized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum YearOfIncorporation {
+    /// More than five years ago.
+    OverFiveYears,
+    /// Within the last five years, in the year given if any.
+    WithinFiveYears(Option<i32>),
+    /// The issuer is yet to be formed.
+    YetToBeFormed,
+}
+
+/// An issuer of the offering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormDIssuer {
+    /// The CIK with leading zeros.
+    pub cik: String,
+    /// The name of the issuer.
+    pub name: String,
+    /// The principal place of business, with the phone number of the issuer.
+    pub address: Option<Address>,
+    /// The jurisdiction of incorporation or organization, such as `DELAWARE`.
+    pub jurisdiction_of_incorporation: Option<String>,
+    /// The kind of entity, such as `Corporation` or `Limited Partnership`.
+    pub entity_type: Option<String>,
+    /// See [YearOfIncorporation]
+    pub year_of_incorporation: Option<YearOfIncorporation>,
+    /// The names the issuer was previously known by.
+    pub previous_names: Vec<String>,
+}
+
+/// How a related person is related to the issuer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelatedPersonRelationship {
+    /// `Executive Officer`
+    ExecutiveOfficer,
+    /// `Director`
+    Director,
+    /// `Promoter`
+    Promoter,
+    /// Any other relationship, as written in the document.
+    Other(String),
+}
+impl From<&str> for RelatedPersonRelationship {
+    fn from(relationship: &str) -> Self {
+        match relationship.trim() {
+            "Executive Officer" => Self::ExecutiveOfficer,
+            "Director" => Self::Director,
+            "Promoter" => Self::Promoter,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// An executive officer, director or promoter of the issuer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelatedPerson {
+    /// The first name, for people.
+    pub first_name: Option<String>,
+    /// The middle name.
+    pub middle_name: Option<String>,
+    /// The last name, or the name of an entity.
+    pub last_name: String,
+    /// The street address of the person.
+    pub address: Option<Address>,
+    /// See [RelatedPersonRelationship]
+    pub relationships: Vec<RelatedPersonRelationship>,
+    /// The clarification of the relationships, such as a title.
+    pub relationship_clarification: Option<String>,
+}
+
+/// The industry of the issuer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndustryGroup {
+    /// The industry group, such as `Biotechnology` or `Pooled Investment Fund`.
+    pub industry_group_type: String,
+    /// The kind of fund, such as `Hedge Fund` or `Private Equity Fund`, for pooled investment funds.
+    pub investment_fund_type: Option<String>,
+    /// Whether the fund is registered, or will be, as an investment company under the Investment Company Act of 1940.
+    pub is_40_act: Option<bool>,
+}
+
+/// The revenue of the issuer, as a range.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RevenueRange {
+    /// `No Revenues`
+    NoRevenues,
+    /// `$1 - $1,000,000`
+    UpTo1Million,
+    /// `$1,000,001 - $5,000,000`
+    From1MillionTo5Million,
+    /// `$5,000,001 - $25,000,000`
+    From5MillionTo25Million,
+    /// `$25,000,001 - $100,000,000`
+    From25MillionTo100Million,
+    /// `Over $100,000,000`
+    Over100Million,
+    /// `Decline to Disclose`
+    DeclineToDisclose,
+    /// `Not Applicable`
+    NotApplicable,
+    /// Any other range, as written in the document.
+    Other(String),
+}
+impl From<&str> for RevenueRange {
+    fn from(range: &str) -> Self {
+        match range.trim() {
+            "No Revenues" => Self::NoRevenues,
+            "$1 - $1,000,000" => Self::UpTo1Million,
+            "$1,000,001 - $5,000,000" => Self::From1MillionTo5Million,
+            "$5,000,001 - $25,000,000" => Self::From5MillionTo25Million,
+            "$25,000,001 - $100,000,000" => Self::From25MillionTo100Million,
+            "Over $100,000,000" => Self::Over100Million,
+            "Decline to Disclose" => Self::DeclineToDisclose,
+            "Not Applicable" => Self::NotApplicable,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// A federal exemption or exclusion claimed for the offering.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FederalExemption {
+    /// `04`: Rule 504, with the paragraph claimed such as `04.1`.
+    Rule504(Option<String>),
+    /// `06b`: Rule 506(b).
+    Rule506b,
+    /// `06c`: Rule 506(c).
+    Rule506c,
+    /// `4a5`: Securities Act Section 4(a)(5).
+    Section4a5,
+    /// `3C`: Investment Company Act Section 3(c), with the paragraph claimed such as `3C.7` for 3(c)(7).
+    InvestmentCompanyAct3c(Option<u8>),
+    /// Any other exemption, as written in the document.
+    Other(String),
+}
+impl From<&str> for FederalExemption {
+    fn from(item: &str) -> Self {
+        match item.trim() {
+            "04" => Self::Rule504(None),
+            "06b" => Self::Rule506b,
+            "06c" => Self::Rule506c,
+            "4a5" => Self::Section4a5,
+            "3C" => Self::InvestmentCompanyAct3c(None),
+            item if item.starts_with("04.") => Self::Rule504(Some(item.to_string())),
+            item => match item.strip_prefix("3C.").map(str::parse) {
+                Some(Ok(paragraph)) => Self::InvestmentCompanyAct3c(Some(paragraph)),
+                _ => Self::Other(item.to_string()),
+            },
+        }
+    }
+}
+
+/// A kind of security offered.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SecurityType {
+    /// Equity.
+    Equity,
+    /// Debt.
+    Debt,
+    /// Options, warrants or other rights to acquire another security.
+    OptionToAcquire,
+    /// The security to be acquired on the exercise of an option, warrant or other right.
+    SecurityToBeAcquired,
+    /// Interests in a pooled investment fund.
+    PooledInvestmentFund,
+    /// Tenant-in-common securities.
+    TenantInCommon,
+    /// Mineral property securities.
+    MineralProperty,
+    /// Any other kind, as described in the document.
+    Other(Option<String>),
+}
+
+/// An amount of the offering, which may be left indefinite.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OfferingAmount {
+    /// An amount in dollars.
+    Dollars(u64),
+    /// `Indefinite`, such as for funds that accept investments continuously.
+    Indefinite,
+}
+
+/// The amounts of the offering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingAmounts {
+    /// The total amount offered.
+    pub total_offering_amount: OfferingAmount,
+    /// The amount sold so far in dollars.
+    pub total_amount_sold: u64,
+    /// The amount remaining to be sold.
+    pub total_remaining: OfferingAmount,
+    /// The clarification of the amounts.
+    pub clarification: Option<String>,
+}
+
+/// The investors of the offering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Investors {
+    /// Whether securities were sold, or may be sold, to investors who do not qualify as accredited investors.
+    pub has_non_accredited_investors: bool,
+    /// The number of investors who do not qualify as accredited investors.
+    pub number_non_accredited_investors: Option<u64>,
+    /// The number of investors who already invested.
+    pub total_number_already_invested: u64,
+}
+
+/// A person paid a commission for the sale of securities of the offering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SalesCompensationRecipient {
+    /// The name of the recipient.
+    pub name: String,
+    /// The CRD number of the recipient.
+    pub crd_number: Option<String>,
+    /// The name of the broker-dealer the recipient is associated with.
+    pub associated_broker_dealer_name: Option<String>,
+    /// The CRD number of the broker-dealer the recipient is associated with.
+    pub associated_broker_dealer_crd_number: Option<String>,
+    /// The street address of the recipient.
+    pub address: Option<Address>,
+    /// The states in which the recipient solicited or will solicit purchasers.
+    pub states_of_solicitation: Vec<String>,
+    /// Whether the recipient solicited or will solicit in all states.
+    pub all_states: bool,
+    /// Whether the recipient solicited or will solicit outside the United States.
+    pub foreign_solicitation: bool,
+}
+
+/// The signature of the issuer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormDSignature {
+    /// The issuer signed for.
+    pub issuer_name: String,
+    /// The signature, such as `/s/ Maria L. Okafor`.
+    pub signature_name: String,
+    /// The name of the signer.
+    pub name_of_signer: String,
+    /// The title of the signer.
+    pub title: Option<String>,
+    /// The date of the signature.
+    pub date: Option<NaiveDate>,
+}
+
+/// A Form D.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormD {
+    /// The version of the schema, such as `X0708`.
+    pub schema_version: Option<String>,
+    /// The form, `D` or `D/A`.
+    pub submission_type: String,
+    /// See [FormDIssuer]
+    pub primary_issuer: FormDIssuer,
+    /// The other issuers of the offering.
+    pub other_issuers: Vec<FormDIssuer>,
+    /// See [RelatedPerson]
+    pub related_persons: Vec<RelatedPerson>,
+    /// See [IndustryGroup]
+    pub industry_group: IndustryGroup,
+    /// See [RevenueRange]. None for pooled investment funds, which give `aggregate_net_asset_value_range` instead.
+    pub revenue_range: Option<RevenueRange>,
+    /// The net asset value of a fund, as a range such as `$5,000,001 - $25,000,000`.
+    pub aggregate_net_asset_value_range: Option<String>,
+    /// See [FederalExemption]
+    pub federal_exemptions: Vec<FederalExemption>,
+    /// Whether the notice amends an earlier one.
+    pub is_amendment: bool,
+    /// The accession number of the notice amended.
+    pub previous_accession_number: Option<AccessionNumber>,
+    /// The date of the first sale. None if the first sale is yet to occur.
+    pub date_of_first_sale: Option<NaiveDate>,
+    /// Whether the offering is expected to last more than one year.
+    pub more_than_one_year: bool,
+    /// See [SecurityType]
+    pub securities_offered: Vec<SecurityType>,
+    /// Whether the offering is made in connection with a business combination, such as a merger.
+    pub is_business_combination: bool,
+    /// The minimum investment accepted from any outside investor in dollars.
+    pub minimum_investment_accepted: Option<u64>,
+    /// See [SalesCompensationRecipient]
+    pub sales_compensation: Vec<SalesCompensationRecipient>,
+    /// See [OfferingAmounts]
+    pub offering_amounts: OfferingAmounts,
+    /// See [Investors]
+    pub investors: Investors,
+    /// The sales commissions paid in dollars.
+    pub sales_commissions: Option<u64>,
+    /// The finders' fees paid in dollars.
+    pub finders_fees: Option<u64>,
+    /// The gross proceeds used for payments to related persons in dollars.
+    pub gross_proceeds_used: Option<u64>,
+    /// See [FormDSignature]
+    pub signatures: Vec<FormDSignature>,
+}
+impl FormD {
+    /// Parses the XML of a Form D.
+    pub fn new(xml: &str) -> Result<Self, EDGARError> {
+        let raw: RawEdgarSubmission = from_str(xml.trim())?;
+        let offering = raw.offering_data;
+        let filing = offering.type_of_filing;
+        let amounts = offering.offering_sales_amounts;
+        let fees = offering.sales_commissions_finders_fees.unwrap_or_default();
+        Ok(Self {
+            schema_version: non_empty(raw.schema_version),
+            submission_type: raw.submission_type.trim().to_string(),
+            primary_issuer: raw.primary_issuer.into_issuer(),
+            other_issuers: raw
+                .issuer_list
+                .map(|l| l.issuer)
+                .unwrap_or_default()
+                .into_iter()
+                .map(RawIssuer::into_issuer)
+                .collect(),
+            related_persons: raw
+                .related_persons_list
+                .map(|l| l.related_person_info)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| RelatedPerson {
+                    first_name: non_empty(p.related_person_name.first_name),
+                    middle_name: non_empty(p.related_person_name.middle_name),
+                    last_name: p.related_person_name.last_name.trim().to_string(),
+                    address: p
+                        .related_person_address
+                        .map(|a| a.into_address("relatedPersonAddress", None)),
+                    relationships: p
+                        .related_person_relationship_list
+                        .map(|l| l.relationship)
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|r| r.as_str().into())
+                        .collect(),
+                    relationship_clarification: non_empty(p.relationship_clarification),
+                })
+                .collect(),
+            industry_group: IndustryGroup {
+                industry_group_type: offering
+                    .industry_group
+                    .industry_group_type
+                    .trim()
+                    .to_string(),
+                investment_fund_type: offering
+                    .industry_group
+                    .investment_fund_info
+                    .as_ref()
+                    .and_then(|f| non_empty(f.investment_fund_type.clone())),
+                is_40_act: offering
+                    .industry_group
+                    .investment_fund_info
+                    .and_then(|f| f.is40_act)
+                    .map(|f| flag(Some(f))),
+            },
+            revenue_range: offering
+                .issuer_size
+                .as_ref()
+                .and_then(|s| non_empty(s.revenue_range.clone()))
+                .map(|r| r.as_str().into()),
+            aggregate_net_asset_value_range: offering
+                .issuer_size
+                .and_then(|s| non_empty(s.aggregate_net_asset_value_range)),
+            federal_exemptions: offering
+                .federal_exemptions_exclusions
+                .map(|f| f.item)
+                .unwrap_or_default()
+                .iter()
+                .map(|i| i.as_str().into())
+                .collect(),
+            is_amendment: flag(filing.new_or_amendment.is_amendment),
+            previous_accession_number: non_empty(filing.new_or_amendment.previous_accession_number)
+                .map(|a| a.parse())
+                .transpose()?,
+            date_of_first_sale: filing
+                .date_of_first_sale
+                .and_then(|d| non_empty(d.value))
+                .map(|d| date(&d))
+                .transpose()?,
+            more_than_one_year: flag(
+                offering
+                    .duration_of_offering
+                    .and_then(|d| d.more_than_one_year),
+            ),
+            securities_offered: offering
+                .types_of_securities_offered
+                .map(RawTypesOfSecuritiesOffered::into_types)
+                .unwrap_or_default(),
+            is_business_combination: flag(
+                offering
+                    .business_combination_transaction
+                    .and_then(|b| b.is_business_combination_transaction),
+            ),
+            minimum_investment_accepted: number(
+                offering.minimum_investment_accepted.as_deref(),
+                "minimum investment accepted",
+            )?,
+            sales_compensation: offering
+                .sales_compensation_list
+                .map(|l| l.recipient)
+                .unwrap_or_default()
+                .into_iter()
+                .map(RawRecipient::into_recipient)
+                .collect(),
+            offering_amounts: OfferingAmounts {
+                total_offering_amount: offering_amount(
+                    &amounts.total_offering_amount,
+                    "total offering amount",
+                )?,
+                total_amount_sold: number(Some(&amounts.total_amount_sold), "total amount sold")?
+                    .unwrap_or_default(),
+                total_remaining: offering_amount(&amounts.total_remaining, "total remaining")?,
+                clarification: non_empty(amounts.clarification_of_response),
+            },
+            investors: Investors {
+                has_non_accredited_investors: flag(offering.investors.has_non_accredited_investors),
+                number_non_accredited_investors: number(
+                    offering
+                        .investors
+                        .number_non_accredited_investors
+                        .as_deref(),
+                    "number of non-accredited investors",
+                )?,
+                total_number_already_invested: number(
+                    Some(&offering.investors.total_number_already_invested),
+                    "total number already invested",
+                )?
+                .unwrap_or_default(),
+            },
+            sales_commissions: number(
+                fees.sales_commissions
+                    .and_then(|f| f.dollar_amount)
+                    .as_deref(),
+                "sales commissions",
+            )?,
+            finders_fees: number(
+                fees.finders_fees.and_then(|f| f.dollar_amount).as_deref(),
+                "finders' fees",
+            )?,
+            gross_proceeds_used: number(
+                offering
+                    .use_of_proceeds
+                    .and_then(|u| u.gross_proceeds_used)
+                    .and_then(|g| g.dollar_amount)
+                    .as_deref(),
+                "gross proceeds used",
+            )?,
+            signatures: offering
+                .signature_block
+                .map(|s| s.signature)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| {
+                    Ok(FormDSignature {
+                        issuer_name: s.issuer_name.trim().to_string(),
+                        signature_name: s.signature_name.trim().to_string(),
+                        name_of_signer: s.name_of_signer.trim().to_string(),
+                        title: non_empty(s.signature_title),
+                        date: non_empty(s.signature_date).map(|d| date(&d)).transpose()?,
+                    })
+                })
+                .collect::<Result<_, EDGARError>>()?,
+        })
+    }
+}
+
+/// Get the Form D filing `accession_number` of the issuer with `cik`.
+///
+/// The form is read from the XML files in the folder of the filing, or from the complete submission text file of older filings without them.
+pub async fn get_form_d(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+) -> Result<FormD, EDGARError> {
+    let files = get_xml_files(client.clone(), cik, accession_number, |d| {
+        d.file_name == "primary_doc.xml"
+    })
+    .await?;
+    for text in files.map(|files| files.texts).unwrap_or_default() {
+        if is_form_d(&text) {
+            return FormD::new(&text);
+        }
+    }
+    let mut reader = get_complete_submission(client, cik, accession_number).await?;
+    while let Some(document) = reader.next_document().await? {
+        let text = document.text();
+        if is_form_d(&text) {
+            return FormD::new(&text);
+        }
+    }
+    Err(EDGARError::FormDNotFound)
+}
+fn is_form_d(xml: &str) -> bool {
+    has_element(xml, "edgarSubmission") && has_element(xml, "offeringData")
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEdgarSubmission {
+    schema_version: Option<String>,
+    submission_type: String,
+    primary_issuer: RawIssuer,
+    issuer_list: Option<RawIssuerList>,
+    related_persons_list: Option<RawRelatedPersonsList>,
+    offering_data: RawOfferingData,
+}
+#[derive(Debug, Deserialize)]
+struct RawIssuerList {
+    #[serde(default)]
+    issuer: Vec<RawIssuer>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawIssuer {
+    cik: String,
+    entity_name: String,
+    issuer_address: Option<RawAddress>,
+    issuer_phone_number: Option<String>,
+    jurisdiction_of_inc: Option<String>,
+    issuer_previous_name_list: Option<RawPreviousNameList>,
+    entity_type: Option<String>,
+    year_of_inc: Option<RawYearOfInc>,
+}
+impl RawIssuer {
+    fn into_issuer(self) -> FormDIssuer {
+        let phone = non_empty(self.issuer_phone_number);
+        FormDIssuer {
+            cik: add_leading_zeros_to_cik(self.cik.trim()),
+            name: self.entity_name.trim().to_string(),
+            address: self
+                .issuer_address
+                .map(|a| a.into_address("issuerAddress", phone)),
+            jurisdiction_of_incorporation: non_empty(self.jurisdiction_of_inc),
+            entity_type: non_empty(self.entity_type),
+            year_of_incorporation: self.year_of_inc.and_then(|y| {
+                if flag(y.over_five_years) {
+                    Some(YearOfIncorporation::OverFiveYears)
+                } else if flag(y.within_five_years) {
+                    let year = non_empty(y.value).and_then(|v| v.parse().ok());
+                    Some(YearOfIncorporation::WithinFiveYears(year))
+                } else if flag(y.yet_to_be_formed) {
+                    Some(YearOfIncorporation::YetToBeFormed)
+                } else {
+                    None
+                }
+            }),
+            previous_names: self
+                .issuer_previous_name_list
+                .map(|l| l.previous_name)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|n| non_empty(Some(n)))
+                .collect(),
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPreviousNameList {
+    #[serde(default)]
+    previous_name: Vec<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawYearOfInc {
+    over_five_years: Option<String>,
+    within_five_years: Option<String>,
+    yet_to_be_formed: Option<String>,
+    value: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAddress {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    zip_code: Option<String>,
+}
+impl RawAddress {
+    /// `element` is the name of the address element. The principal place of business of an issuer is its business address,
+    /// while the addresses of people and recipients are kept as [AddressType::Other] with that name.
+    fn into_address(self, element: &str, phone: Option<String>) -> Address {
+        Address {
+            address_type: match element {
+                "issuerAddress" => AddressType::Business,
+                other => AddressType::Other(other.to_string()),
+            },
+            street1: non_empty(self.street1),
+            street2: non_empty(self.street2),
+            city: non_empty(self.city),
+            state: non_empty(self.state_or_country),
+            zip: non_empty(self.zip_code),
+            phone,
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRelatedPersonsList {
+    #[serde(default)]
+    related_person_info: Vec<RawRelatedPerson>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRelatedPerson {
+    related_person_name: RawRelatedPersonName,
+    related_person_address: Option<RawAddress>,
+    related_person_relationship_list: Option<RawRelationshipList>,
+    relationship_clarification: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRelatedPersonName {
+    first_name: Option<String>,
+    middle_name: Option<String>,
+    #[serde(default)]
+    last_name: String,
+}
+#[derive(Debug, Deserialize)]
+struct RawRelationshipList {
+    #[serde(default)]
+    relationship: Vec<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOfferingData {
+    industry_group: RawIndustryGroup,
+    issuer_size: Option<RawIssuerSize>,
+    federal_exemptions_exclusions: Option<RawFederalExemptions>,
+    type_of_filing: RawTypeOfFiling,
+    duration_of_offering: Option<RawDurationOfOffering>,
+    types_of_securities_offered: Option<RawTypesOfSecuritiesOffered>,
+    business_combination_transaction: Option<RawBusinessCombination>,
+    minimum_investment_accepted: Option<String>,
+    sales_compensation_list: Option<RawSalesCompensationList>,
+    offering_sales_amounts: RawOfferingSalesAmounts,
+    investors: RawInvestors,
+    sales_commissions_finders_fees: Option<RawCommissionsFees>,
+    use_of_proceeds: Option<RawUseOfProceeds>,
+    signature_block: Option<RawSignatureBlock>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawIndustryGroup {
+    #[serde(default)]
+    industry_group_type: String,
+    investment_fund_info: Option<RawInvestmentFundInfo>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInvestmentFundInfo {
+    investment_fund_type: Option<String>,
+    #[serde(rename = "is40Act")]
+    is40_act: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawIssuerSize {
+    revenue_range: Option<String>,
+    aggregate_net_asset_value_range: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFederalExemptions {
+    #[serde(default)]
+    item: Vec<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTypeOfFiling {
+    new_or_amendment: RawNewOrAmendment,
+    date_of_first_sale: Option<RawDateOfFirstSale>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawNewOrAmendment {
+    is_amendment: Option<String>,
+    previous_accession_number: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawDateOfFirstSale {
+    value: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDurationOfOffering {
+    more_than_one_year: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTypesOfSecuritiesOffered {
+    is_equity_type: Option<String>,
+    is_debt_type: Option<String>,
+    is_option_to_acquire_type: Option<String>,
+    is_security_to_be_acquired_type: Option<String>,
+    is_pooled_investment_fund_type: Option<String>,
+    is_tenant_in_common_type: Option<String>,
+    is_mineral_property_type: Option<String>,
+    is_other_type: Option<String>,
+    description_of_other_type: Option<String>,
+}
+impl RawTypesOfSecuritiesOffered {
+    fn into_types(self) -> Vec<SecurityType> {
+        [
+            (self.is_equity_type, SecurityType::Equity),
+            (self.is_debt_type, SecurityType::Debt),
+            (
+                self.is_option_to_acquire_type,
+                SecurityType::OptionToAcquire,
+            ),
+            (
+                self.is_security_to_be_acquired_type,
+                SecurityType::SecurityToBeAcquired,
+            ),
+            (
+                self.is_pooled_investment_fund_type,
+                SecurityType::PooledInvestmentFund,
+            ),
+            (self.is_tenant_in_common_type, SecurityType::TenantInCommon),
+            (self.is_mineral_property_type, SecurityType::MineralProperty),
+            (
+                self.is_other_type,
+                SecurityType::Other(non_empty(self.description_of_other_type)),
+            ),
+        ]
+        .into_iter()
+        .filter(|(is_type, _)| flag(is_type.clone()))
+        .map(|(_, security_type)| security_type)
+        .collect()
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBusinessCombination {
+    is_business_combination_transaction: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawSalesCompensationList {
+    #[serde(default)]
+    recipient: Vec<RawRecipient>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRecipient {
+    #[serde(default)]
+    recipient_name: String,
+    #[serde(rename = "recipientCRDNumber")]
+    recipient_crd_number: Option<String>,
+    #[serde(rename = "associatedBDName")]
+    associated_bd_name: Option<String>,
+    #[serde(rename = "associatedBDCRDNumber")]
+    associated_bd_crd_number: Option<String>,
+    recipient_address: Option<RawAddress>,
+    states_of_solicitation_list: Option<RawStatesOfSolicitation>,
+    foreign_solicitation: Option<String>,
+}
+impl RawRecipient {
+    fn into_recipient(self) -> SalesCompensationRecipient {
+        let states = self.states_of_solicitation_list;
+        let all_states = states
+            .as_ref()
+            .and_then(|s| s.value.as_deref())
+            .is_some_and(|v| v.trim() == "All States");
+        SalesCompensationRecipient {
+            name: self.recipient_name.trim().to_string(),
+            crd_number: not_none(self.recipient_crd_number),
+            associated_broker_dealer_name: not_none(self.associated_bd_name),
+            associated_broker_dealer_crd_number: not_none(self.associated_bd_crd_number),
+            address: self
+                .recipient_address
+                .map(|a| a.into_address("recipientAddress", None)),
+            states_of_solicitation: states
+                .map(|s| s.state)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|s| non_empty(Some(s)))
+                .collect(),
+            all_states,
+            foreign_solicitation: flag(self.foreign_solicitation),
+        }
+    }
+}
+#[derive(Debug, Deserialize)]
+struct RawStatesOfSolicitation {
+    #[serde(default)]
+    state: Vec<String>,
+    value: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOfferingSalesAmounts {
+    #[serde(default)]
+    total_offering_amount: String,
+    #[serde(default)]
+    total_amount_sold: String,
+    #[serde(default)]
+    total_remaining: String,
+    clarification_of_response: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInvestors {
+    has_non_accredited_investors: Option<String>,
+    number_non_accredited_investors: Option<String>,
+    #[serde(default)]
+    total_number_already_invested: String,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCommissionsFees {
+    sales_commissions: Option<RawDollarAmount>,
+    finders_fees: Option<RawDollarAmount>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDollarAmount {
+    dollar_amount: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawUseOfProceeds {
+    gross_proceeds_used: Option<RawDollarAmount>,
+}
+#[derive(Debug, Deserialize)]
+struct RawSignatureBlock {
+    #[serde(default)]
+    signature: Vec<RawSignature>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSignature {
+    #[serde(default)]
+    issuer_name: String,
+    #[serde(default)]
+    signature_name: String,
+    #[serde(default)]
+    name_of_signer: String,
+    signature_title: Option<String>,
+    signature_date: Option<String>,
+}
+
+/// Some optional fields are written as `None` when they do not apply.
+fn not_none(value: Option<String>) -> Option<String> {
+    non_empty(value).filter(|v| !v.eq_ignore_ascii_case("none"))
+}
+fn offering_amount(value: &str, field: &str) -> Result<OfferingAmount, EDGARError> {
+    match value.trim() {
+        "Indefinite" => Ok(OfferingAmount::Indefinite),
+        value => number(Some(value), field)?
+            .map(OfferingAmount::Dollars)
+            .ok_or_else(|| invalid_field(field, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{
+        client, filing_index, xml_submission, TestResponse, TestServer,
+    };
+
+    const PRIMARY_DOC: &str = include_str!("../tests/fixtures/form_d/primary_doc.xml");
+
+    #[test]
+    fn form_d_issuer_and_related_persons() {
+        let form_d = FormD::new(PRIMARY_DOC).unwrap();
+        assert_eq!(form_d.submission_type, "D");
+        let issuer = &form_d.primary_issuer;
+        assert_eq!(issuer.cik, "0001987654");
+        assert_eq!(issuer.name, "Northwind Therapeutics, Inc.");
+        let address = issuer.address.as_ref().unwrap();
+        assert_eq!(address.zip.as_deref(), Some("02142"));
+        assert_eq!(address.phone.as_deref(), Some("617-555-0142"));
+        assert_eq!(address.address_type, AddressType::Business);
+        assert_eq!(
+            issuer.year_of_incorporation,
+            Some(YearOfIncorporation::WithinFiveYears(Some(2021)))
+        );
+        assert_eq!(issuer.previous_names, vec!["Northwind Bio, Inc."]);
+        assert!(form_d.other_issuers.is_empty());
+        assert_eq!(form_d.related_persons.len(), 2);
+        let ceo = &form_d.related_persons[0];
+        assert_eq!(ceo.middle_name.as_deref(), Some("L."));
+        assert_eq!(
+            ceo.relationships,
+            vec![
+                RelatedPersonRelationship::ExecutiveOfficer,
+                RelatedPersonRelationship::Director
+            ]
+        );
+        assert_eq!(
+            ceo.address.as_ref().unwrap().address_type,
+            AddressType::Other("relatedPersonAddress".to_string())
+        );
+        let director = &form_d.related_persons[1];
+        assert_eq!(director.middle_name, None);
+        assert_eq!(director.relationship_clarification, None);
+        assert_eq!(
+            form_d.signatures[0].date,
+            NaiveDate::from_ymd_opt(2023, 7, 27)
+        )
+    }
+    #[test]
+    fn form_d_offering() {
+        let form_d = FormD::new(PRIMARY_DOC).unwrap();
+        assert_eq!(form_d.industry_group.industry_group_type, "Biotechnology");
+        assert_eq!(form_d.industry_group.is_40_act, None);
+        assert_eq!(form_d.revenue_range, Some(RevenueRange::UpTo1Million));
+        assert_eq!(
+            form_d.federal_exemptions,
+            vec![
+                FederalExemption::Rule506b,
+                FederalExemption::InvestmentCompanyAct3c(None),
+                FederalExemption::InvestmentCompanyAct3c(Some(1)),
+            ]
+        );
+        assert!(!form_d.is_amendment);
+        assert_eq!(
+            form_d.date_of_first_sale,
+            NaiveDate::from_ymd_opt(2023, 7, 14)
+        );
+        assert_eq!(
+            form_d.securities_offered,
+            vec![
+                SecurityType::Equity,
+                SecurityType::OptionToAcquire,
+                SecurityType::SecurityToBeAcquired
+            ]
+        );
+        assert_eq!(form_d.minimum_investment_accepted, Some(25000));
+        assert_eq!(
+            form_d.offering_amounts.total_offering_amount,
+            OfferingAmount::Dollars(20000000)
+        );
+        assert_eq!(form_d.offering_amounts.total_amount_sold, 12500000);
+        assert_eq!(
+            form_d.offering_amounts.total_remaining,
+            OfferingAmount::Dollars(7500000)
+        );
+        assert!(form_d.investors.has_non_accredited_investors);
+        assert_eq!(form_d.investors.number_non_accredited_investors, Some(2));
+        assert_eq!(form_d.investors.total_number_already_invested, 23);
+        assert_eq!(form_d.sales_commissions, Some(375000));
+        assert_eq!(form_d.finders_fees, Some(0));
+        let recipient = &form_d.sales_compensation[0];
+        assert_eq!(recipient.crd_number.as_deref(), Some("123456"));
+        assert_eq!(recipient.associated_broker_dealer_name, None);
+        assert_eq!(recipient.states_of_solicitation, vec!["MA", "NY"]);
+        assert!(!recipient.all_states);
+        assert_eq!(
+            recipient.address.as_ref().unwrap().address_type,
+            AddressType::Other("recipientAddress".to_string())
+        )
+    }
+    #[test]
+    fn form_d_amendment() {
+        let xml = PRIMARY_DOC.replace(
+            "<isAmendment>false</isAmendment>",
+            "<isAmendment>true</isAmendment><previousAccessionNumber>0001987654-23-000001</previousAccessionNumber>",
+        );
+        let form_d = FormD::new(&xml).unwrap();
+        assert!(form_d.is_amendment);
+        let previous = form_d.previous_accession_number.unwrap();
+        assert_eq!(previous.value, "0001987654-23-000001");
+        assert_eq!(previous.filer_id, "0001987654");
+        assert!(FormD::new(&xml.replace("0001987654-23-000001", "not a number")).is_err())
+    }
+    #[test]
+    fn form_d_is_form_d() {
+        assert!(is_form_d(PRIMARY_DOC));
+        assert!(is_form_d(
+            "<ns1:edgarSubmission><ns1:offeringData></ns1:offeringData></ns1:edgarSubmission>"
+        ));
+        assert!(!is_form_d(
+            "<edgarSubmission><formData></formData></edgarSubmission>"
+        ))
+    }
+    #[test]
+    fn form_d_pooled_investment_fund() {
+        let xml = PRIMARY_DOC
+            .replace(
+                "<industryGroupType>Biotechnology</industryGroupType>",
+                "<industryGroupType>Pooled Investment Fund</industryGroupType>\
+                 <investmentFundInfo><investmentFundType>Hedge Fund</investmentFundType>\
+                 <is40Act>false</is40Act></investmentFundInfo>",
+            )
+            .replace(
+                "<revenueRange>$1 - $1,000,000</revenueRange>",
+                "<aggregateNetAssetValueRange>Decline to Disclose</aggregateNetAssetValueRange>",
+            )
+            .replace(
+                "<totalOfferingAmount>20000000</totalOfferingAmount>",
+                "<totalOfferingAmount>Indefinite</totalOfferingAmount>",
+            )
+            .replace(
+                "<totalRemaining>7500000</totalRemaining>",
+                "<totalRemaining>Indefinite</totalRemaining>",
+            )
+            .replace(
+                "<state>MA</state>\n          <state>NY</state>",
+                "<value>All States</value>",
+            )
+            .replace(
+                "<dateOfFirstSale>\n        <value>2023-07-14</value>",
+                "<dateOfFirstSale>\n        <yetToOccur>true</yetToOccur>",
+            );
+        let form_d = FormD::new(&xml).unwrap();
+        assert_eq!(
+            form_d.industry_group.investment_fund_type.as_deref(),
+            Some("Hedge Fund")
+        );
+        assert_eq!(form_d.industry_group.is_40_act, Some(false));
+        assert_eq!(form_d.revenue_range, None);
+        assert_eq!(
+            form_d.aggregate_net_asset_value_range.as_deref(),
+            Some("Decline to Disclose")
+        );
+        assert_eq!(
+            form_d.offering_amounts.total_offering_amount,
+            OfferingAmount::Indefinite
+        );
+        assert_eq!(
+            form_d.offering_amounts.total_remaining,
+            OfferingAmount::Indefinite
+        );
+        assert_eq!(form_d.date_of_first_sale, None);
+        let recipient = &form_d.sales_compensation[0];
+        assert!(recipient.all_states);
+        assert!(recipient.states_of_solicitation.is_empty())
+    }
+    #[test]
+    fn form_d_federal_exemptions() {
+        assert_eq!(
+            FederalExemption::from("04.2"),
+            FederalExemption::Rule504(Some("04.2".to_string()))
+        );
+        assert_eq!(FederalExemption::from("06c"), FederalExemption::Rule506c);
+        assert_eq!(FederalExemption::from("4a5"), FederalExemption::Section4a5);
+        assert_eq!(
+            FederalExemption::from("3C.7"),
+            FederalExemption::InvestmentCompanyAct3c(Some(7))
+        );
+        assert_eq!(
+            FederalExemption::from("3C.x"),
+            FederalExemption::Other("3C.x".to_string())
+        )
+    }
+    #[tokio::test]
+    async fn form_d_get_form_d() {
+        let folder = "/Archives/edgar/data/1987654/000198765423000001";
+        let index = filing_index(
+            "2023-07-27",
+            &[("D", "primary_doc.html"), ("D", "primary_doc.xml")],
+        );
+        let server = TestServer::start()
+            .route(
+                &format!("{folder}/0001987654-23-000001-index.htm"),
+                vec![TestResponse::ok("text/html", &index)],
+            )
+            .route(
+                &format!("{folder}/primary_doc.xml"),
+                vec![TestResponse::ok("text/xml", PRIMARY_DOC.as_bytes())],
+            );
+        let accession_number = "0001987654-23-000001".parse().unwrap();
+        let form_d = get_form_d(client(&server), "0001987654", &accession_number)
+            .await
+            .unwrap();
+        assert_eq!(form_d, FormD::new(PRIMARY_DOC).unwrap());
+        // The complete submission is not downloaded when the folder has the form.
+        assert!(server
+            .requests()
+            .iter()
+            .all(|request| !request[0].contains(".txt")));
+        // Older filings have no -index.htm and are read from the complete submission.
+        let submission = xml_submission(
+            "0001987654-23-000001",
+            "D",
+            "20230727",
+            &[("D", PRIMARY_DOC)],
+        );
+        let server = TestServer::start().route(
+            &format!("{folder}/0001987654-23-000001.txt"),
+            vec![TestResponse::ok("text/plain", &submission)],
+        );
+        let form_d = get_form_d(client(&server), "0001987654", &accession_number)
+            .await
+            .unwrap();
+        assert_eq!(form_d, FormD::new(PRIMARY_DOC).unwrap());
+        let server = TestServer::start().route(
+            &format!("{folder}/0001987654-23-000001.txt"),
+            vec![TestResponse::ok(
+                "text/plain",
+                &xml_submission("0001987654-23-000001", "D", "20230727", &[]),
+            )],
+        );
+        let missing = get_form_d(client(&server), "1987654", &accession_number).await;
+        assert!(matches!(missing, Err(EDGARError::FormDNotFound)))
+    }
+}
@@ -0,0 +1,432 @@
+//! This module provides EDGAR full-text search, which searches the text of every document filed since 2001.
+//!
+//! Unlike the search text of [EdgarQueryBuilder](crate::edgar_query::edgar_query_builder::EdgarQueryBuilder),
+//! which only matches company names, full-text search is served by `efts.sec.gov`.
+//! ```
+//! use sec_edgar::{
+//!     edgar::edgar_client,
+//!     edgar_query::{edgar_query_builder::BuilderInput, filing::FilingTypeOption::_10K},
+//!     full_text_search::{get_search_results, FullTextSearchBuilder},
+//! };
+//! use chrono::NaiveDate;
+//! async fn some_func() {
+//!     let query = FullTextSearchBuilder::new("")
+//!         .add_phrase("climate risk")
+//!         .add_form(BuilderInput::TypeTInput(_10K))
+//!         .set_date_range(
+//!             NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+//!             NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+//!         )
+//!         .build()
+//!         .unwrap();
+//!     let results = get_search_results(edgar_client().unwrap(), query).await.unwrap();
+//!     for hit in results.hits {
+//!         println!("{} {}", hit.accession_number.value, hit.snippets.join(" ... "));
+//!     }
+//! }
+//! ```
+
+use crate::client::{endpoints::Endpoints, EdgarClient};
+use crate::edgar_query::{
+    edgar_query_builder::{add_leading_zeros_to_cik, BuilderInput},
+    filing::{self, validate_filing_type_string, FilingTypeOption},
+    filing_content_value::AccessionNumber,
+};
+use crate::error::EDGARError;
+use crate::fields::invalid_field;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// The number of hits in a page of search results.
+pub const SEARCH_PAGE_SIZE: u32 = 100;
+
+/// Whether a location filter applies to where the entity is located or where it is incorporated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LocationType {
+    /// The state or country of the entity's business address.
+    Located,
+    /// The state or country the entity is incorporated in.
+    Incorporated,
+}
+
+/// Build a URL HTTPS query that will be used to search the full text of EDGAR filings.
+/// ```
+/// use sec_edgar::full_text_search::{FullTextSearchBuilder, LocationType};
+/// let query = FullTextSearchBuilder::new("bank")
+///     .add_phrase("climate risk")
+///     .add_cik("831001")
+///     .set_location("NY", LocationType::Located)
+///     .set_from(100)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     query.as_str(),
+///     "https://efts.sec.gov/LATEST/search-index?q=bank+%22climate+risk%22&ciks=0000831001&locationCode=NY&locationType=located&from=100"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FullTextSearchBuilder {
+    #[allow(missing_docs)]
+    pub base: Url,
+    #[allow(missing_docs)]
+    pub q: String,
+    #[allow(missing_docs)]
+    pub forms: Vec<String>,
+    #[allow(missing_docs)]
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+    #[allow(missing_docs)]
+    pub ciks: Vec<String>,
+    #[allow(missing_docs)]
+    pub entity_name: String,
+    #[allow(missing_docs)]
+    pub location: Option<(String, LocationType)>,
+    #[allow(missing_docs)]
+    pub from: u32,
+}
+impl FullTextSearchBuilder {
+    /// Instantiates a search for the keywords in `q`, which may also hold quoted phrases and boolean operators such as `OR`.
+    ///
+    /// To search somewhere other than the SEC, see [FullTextSearchBuilder::set_endpoints].
+    pub fn new(q: &str) -> Self {
+        Self {
+            base: search_index_base(&Endpoints::default()).expect("valid search-index URL"),
+            q: q.trim().to_string(),
+            forms: vec![],
+            date_range: None,
+            ciks: vec![],
+            entity_name: Default::default(),
+            location: None,
+            from: 0,
+        }
+    }
+    /// Builds and returns the HTTPS query that can be used to search EDGAR.
+    pub fn build(&self) -> Result<Url, EDGARError> {
+        let mut query = self.base.clone();
+        {
+            let mut pairs = query.query_pairs_mut();
+            pairs.append_pair("q", &self.q);
+            if !self.forms.is_empty() {
+                pairs.append_pair("forms", &self.forms.join(","));
+            }
+            if let Some((start, end)) = self.date_range {
+                pairs
+                    .append_pair("dateRange", "custom")
+                    .append_pair("startdt", &start.format("%Y-%m-%d").to_string())
+                    .append_pair("enddt", &end.format("%Y-%m-%d").to_string());
+            }
+            if !self.ciks.is_empty() {
+                pairs.append_pair("ciks", &self.ciks.join(","));
+            }
+            if !self.entity_name.is_empty() {
+                pairs.append_pair("entityName", &self.entity_name);
+            }
+            if let Some((code, location_type)) = &self.location {
+                let location_type = match location_type {
+                    LocationType::Located => "located",
+                    LocationType::Incorporated => "incorporated",
+                };
+                pairs
+                    .append_pair("locationCode", code)
+                    .append_pair("locationType", location_type);
+            }
+            if self.from > 0 {
+                pairs.append_pair("from", &self.from.to_string());
+            }
+        }
+        Ok(query)
+    }
+    /// Adds an exact phrase that documents must contain.
+    pub fn add_phrase(mut self, phrase: &str) -> Self {
+        let phrase = format!("\"{}\"", phrase.trim().replace('"', ""));
+        self.q = match self.q.is_empty() {
+            true => phrase,
+            false => format!("{} {phrase}", self.q),
+        };
+        self
+    }
+    /// Only returns documents of filings of this type. May be called several times to search several types.
+    ///
+    /// Invalid filing type strings are ignored.
+    pub fn add_form(mut self, filing_type: BuilderInput<FilingTypeOption>) -> Self {
+        let form = match filing_type {
+            BuilderInput::TypeStr(f) => validate_filing_type_string(f).unwrap_or_default(),
+            BuilderInput::TypeTInput(f) => filing::to_string(f),
+        };
+        if !form.is_empty() {
+            self.forms.push(form);
+        }
+        self
+    }
+    /// Only returns documents filed from `start` to `end`, inclusive.
+    ///
+    /// If no date range is set, EDGAR searches the last five years.
+    pub fn set_date_range(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.date_range = Some((start, end));
+        self
+    }
+    /// Only returns documents filed by or about the entity with `cik`. May be called several times.
+    pub fn add_cik(mut self, cik: &str) -> Self {
+        self.ciks.push(add_leading_zeros_to_cik(cik));
+        self
+    }
+    /// Only returns documents filed by or about entities whose name or ticker matches `entity_name`.
+    pub fn set_entity_name(mut self, entity_name: &str) -> Self {
+        self.entity_name = entity_name.to_string();
+        self
+    }
+    /// Only returns documents of entities located or incorporated in the state or country with `code`, such as `NY`.
+    pub fn set_location(mut self, code: &str, location_type: LocationType) -> Self {
+        self.location = Some((code.to_string(), location_type));
+        self
+    }
+    /// The number of hits to skip. Pages hold [SEARCH_PAGE_SIZE] hits, so the second page starts from 100.
+    pub fn set_from(mut self, from: u32) -> Self {
+        self.from = from;
+        self
+    }
+    /// Searches the full-text search service of `endpoints` rather than the SEC's.
+    ///
+    /// Fails if the search path cannot be joined onto the base URL of `efts.sec.gov` in `endpoints`.
+    pub fn set_endpoints(mut self, endpoints: &Endpoints) -> Result<Self, EDGARError> {
+        self.base = search_index_base(endpoints)?;
+        Ok(self)
+    }
+}
+fn search_index_base(endpoints: &Endpoints) -> Result<Url, EDGARError> {
+    endpoints.efts_url("LATEST/search-index")
+}
+
+/// A page of full-text search results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FullTextSearchResults {
+    /// The number of documents that match the search, across every page.
+    pub total: u64,
+    /// See [SearchHit]
+    pub hits: Vec<SearchHit>,
+}
+/// A document that matches a full-text search.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    /// The accession number of the filing the document belongs to.
+    pub accession_number: AccessionNumber,
+    /// The file name of the document in the archives.
+    pub file_name: String,
+    /// The relevance score of the document.
+    pub score: Option<f64>,
+    /// The CIKs of the entities the filing is by or about, with leading zeros.
+    pub ciks: Vec<String>,
+    /// The names of the entities, with their tickers and CIKs, such as `CITIGROUP INC  (C, C-PN)  (CIK 0000831001)`.
+    pub display_names: Vec<String>,
+    /// The form type of the filing, such as `10-Q`.
+    pub form: String,
+    /// The form types of the filing without amendment suffixes.
+    pub root_forms: Vec<String>,
+    /// The type of the document, such as `10-Q` or `EX-21.1`.
+    pub file_type: Option<String>,
+    /// The description of the document.
+    pub file_description: Option<String>,
+    /// The date at which the filing was made.
+    pub file_date: NaiveDate,
+    /// The end of the period the filing reports on, if any.
+    pub period_ending: Option<NaiveDate>,
+    /// The business locations of the entities, such as `New York, NY`.
+    pub biz_locations: Vec<String>,
+    /// The states or countries of incorporation of the entities.
+    pub inc_states: Vec<String>,
+    /// The Standard Industrial Classification codes of the entities.
+    pub sics: Vec<String>,
+    /// The fragments of the document that match the search, with matches wrapped in `<em>`, when EDGAR returns them.
+    pub snippets: Vec<String>,
+}
+impl SearchHit {
+    /// The URL of the document in the archives of `endpoints`.
+    pub fn document_url(&self, endpoints: &Endpoints) -> Result<Url, EDGARError> {
+        let cik = self
+            .ciks
+            .first()
+            .map(|c| c.trim_start_matches('0'))
+            .unwrap_or_default();
+        endpoints.www_url(&format!(
+            "Archives/edgar/data/{cik}/{}/{}",
+            self.accession_number.no_dashes(),
+            self.file_name
+        ))
+    }
+}
+
+/// Get a page of the results of the full-text search `query_url`, as built by [FullTextSearchBuilder].
+pub async fn get_search_results(
+    client: EdgarClient,
+    query_url: Url,
+) -> Result<FullTextSearchResults, EDGARError> {
+    let raw: RawSearchResponse = client.get_json(&query_url).await?;
+    Ok(FullTextSearchResults {
+        total: raw.hits.total.value,
+        hits: raw
+            .hits
+            .hits
+            .into_iter()
+            .map(SearchHit::try_from)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSearchResponse {
+    hits: RawHits,
+}
+#[derive(Debug, Deserialize)]
+struct RawHits {
+    total: RawTotal,
+    #[serde(default)]
+    hits: Vec<RawHit>,
+}
+#[derive(Debug, Deserialize)]
+struct RawTotal {
+    value: u64,
+}
+#[derive(Debug, Deserialize)]
+struct RawHit {
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(rename = "_score")]
+    score: Option<f64>,
+    #[serde(rename = "_source")]
+    source: RawSource,
+    #[serde(default)]
+    highlight: BTreeMap<String, Vec<String>>,
+}
+#[derive(Debug, Deserialize)]
+struct RawSource {
+    adsh: String,
+    #[serde(default)]
+    ciks: Vec<String>,
+    #[serde(default)]
+    display_names: Vec<String>,
+    #[serde(default)]
+    form: String,
+    #[serde(default)]
+    root_forms: Vec<String>,
+    file_type: Option<String>,
+    file_description: Option<String>,
+    file_date: NaiveDate,
+    period_ending: Option<String>,
+    #[serde(default)]
+    biz_locations: Vec<String>,
+    #[serde(default)]
+    inc_states: Vec<String>,
+    #[serde(default)]
+    sics: Vec<String>,
+}
+impl TryFrom<RawHit> for SearchHit {
+    type Error = EDGARError;
+    fn try_from(raw: RawHit) -> Result<Self, Self::Error> {
+        let (_, file_name) = raw
+            .id
+            .split_once(':')
+            .ok_or_else(|| invalid_field("search hit id", &raw.id))?;
+        let source = raw.source;
+        Ok(Self {
+            accession_number: source.adsh.parse()?,
+            file_name: file_name.to_string(),
+            score: raw.score,
+            ciks: source.ciks,
+            display_names: source.display_names,
+            form: source.form,
+            root_forms: source.root_forms,
+            file_type: source.file_type,
+            file_description: source.file_description,
+            file_date: source.file_date,
+            period_ending: source
+                .period_ending
+                .and_then(|p| NaiveDate::parse_from_str(&p, "%Y-%m-%d").ok()),
+            biz_locations: source.biz_locations,
+            inc_states: source.inc_states,
+            sics: source.sics,
+            snippets: raw.highlight.into_values().flatten().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+    use crate::edgar_query::filing::FilingTypeOption::{_10K, _10Q};
+
+    #[test]
+    fn full_text_search_build() {
+        let query = FullTextSearchBuilder::new("")
+            .add_phrase("climate risk")
+            .add_form(BuilderInput::TypeTInput(_10K))
+            .add_form(BuilderInput::TypeStr("10-q"))
+            .add_form(BuilderInput::TypeStr("not a form"))
+            .set_date_range(
+                NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2023, 6, 30).unwrap(),
+            )
+            .set_entity_name("CITIGROUP")
+            .set_location("DE", LocationType::Incorporated)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.as_str(),
+            "https://efts.sec.gov/LATEST/search-index?q=%22climate+risk%22&forms=10-K%2C10-Q&dateRange=custom&startdt=2023-01-01&enddt=2023-06-30&entityName=CITIGROUP&locationCode=DE&locationType=incorporated"
+        )
+    }
+    #[test]
+    fn full_text_search_set_endpoints() {
+        let local = Endpoints::from_base(&Url::parse("http://127.0.0.1:8080").unwrap());
+        let query = FullTextSearchBuilder::new("bank")
+            .set_endpoints(&local)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.as_str(),
+            "http://127.0.0.1:8080/LATEST/search-index?q=bank"
+        );
+        let mailto = Endpoints::new().set_efts(Url::parse("mailto:search@example.com").unwrap());
+        assert!(FullTextSearchBuilder::new("bank")
+            .set_endpoints(&mailto)
+            .is_err())
+    }
+    #[tokio::test]
+    async fn full_text_search_results() {
+        let server = TestServer::start().route(
+            "/LATEST/search-index",
+            vec![TestResponse::ok(
+                "application/json",
+                include_bytes!("../tests/fixtures/efts/search_climate_risk.json"),
+            )],
+        );
+        let endpoints = Endpoints::from_base(server.base());
+        let client = client(&server);
+        let query = FullTextSearchBuilder::new("")
+            .set_endpoints(&endpoints)
+            .unwrap()
+            .add_phrase("climate risk")
+            .add_form(BuilderInput::TypeTInput(_10Q))
+            .build()
+            .unwrap();
+        let results = get_search_results(client, query).await.unwrap();
+        assert_eq!(results.total, 2);
+        let hit = &results.hits[0];
+        assert_eq!(hit.accession_number.value, "0000831001-23-000114");
+        assert_eq!(hit.file_name, "c-20230630.htm");
+        assert_eq!(hit.period_ending, NaiveDate::from_ymd_opt(2023, 6, 30));
+        assert_eq!(
+            hit.snippets,
+            vec!["managing <em>climate risk</em> across the portfolio"]
+        );
+        assert_eq!(
+            hit.document_url(&Endpoints::default()).unwrap().as_str(),
+            "https://www.sec.gov/Archives/edgar/data/831001/000083100123000114/c-20230630.htm"
+        );
+        assert_eq!(results.hits[1].period_ending, None);
+        assert!(results.hits[1].snippets.is_empty());
+        assert!(server.requests()[0][0].contains("forms=10-Q"))
+    }
+}
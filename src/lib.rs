@@ -36,6 +36,15 @@
 
 #![deny(missing_docs)]
 
+pub mod archives;
+pub mod client;
 pub mod edgar;
 pub mod edgar_query;
 pub mod error;
+mod fields;
+pub mod form_13f;
+pub mod form_d;
+pub mod full_text_search;
+pub mod ownership;
+pub mod submissions;
+pub mod xbrl;
@@ -0,0 +1,907 @@
+//! This module parses the ownership documents of insiders, which are the XML of Forms 3, 4 and 5.
+//!
+//! - Form 3 is the initial statement of the holdings of an insider.
+//! - Form 4 reports the changes in the holdings of an insider, within two business days.
+//! - Form 5 is the annual statement of the changes that were not reported on a Form 4.
+//!
+//! Footnotes are resolved to their text wherever they are referenced, see [Footnoted].
+//! ```
+//! use sec_edgar::{edgar::edgar_client, ownership::{get_ownership_document, TransactionCode}};
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let accession_number = "0000320193-23-000076".parse().unwrap();
+//!     let form4 = get_ownership_document(client, "320193", &accession_number)
+//!         .await
+//!         .unwrap();
+//!     for transaction in form4.non_derivative_transactions {
+//!         if transaction.code() == Some(&TransactionCode::OpenMarketSale) {
+//!             println!("{:?} shares at {:?}", transaction.shares.value, transaction.price_per_share.value);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::archives::complete_submission::get_complete_submission;
+use crate::client::EdgarClient;
+use crate::edgar_query::{
+    edgar_query_builder::add_leading_zeros_to_cik, filing_content_value::AccessionNumber,
+};
+use crate::error::EDGARError;
+use crate::fields::{date, flag, invalid_field, non_empty};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_xml_rs::from_str;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// A value of an ownership document, with the text of the footnotes that qualify it.
+///
+/// The value is None when the filer left it out, which usually comes with a footnote that explains why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Footnoted<T> {
+    /// The value.
+    pub value: Option<T>,
+    /// The text of the footnotes referenced by the value.
+    pub footnotes: Vec<String>,
+}
+impl<T> Default for Footnoted<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            footnotes: vec![],
+        }
+    }
+}
+
+/// The code that describes the nature of a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionCode {
+    /// `P`: open market or private purchase.
+    OpenMarketPurchase,
+    /// `S`: open market or private sale.
+    OpenMarketSale,
+    /// `V`: transaction voluntarily reported earlier than required.
+    VoluntarilyReported,
+    /// `A`: grant, award or other acquisition from the issuer under Rule 16b-3(d).
+    Grant,
+    /// `D`: disposition to the issuer under Rule 16b-3(e).
+    DispositionToIssuer,
+    /// `F`: payment of the exercise price or tax liability by delivering or withholding securities.
+    TaxWithholding,
+    /// `I`: discretionary transaction under Rule 16b-3(f).
+    Discretionary,
+    /// `M`: exercise or conversion of a derivative security exempted under Rule 16b-3.
+    ExemptExercise,
+    /// `C`: conversion of a derivative security.
+    Conversion,
+    /// `E`: expiration of a short derivative position.
+    ShortExpiration,
+    /// `H`: expiration or cancellation of a long derivative position with value received.
+    LongExpiration,
+    /// `O`: exercise of an out-of-the-money derivative security.
+    OutOfTheMoneyExercise,
+    /// `X`: exercise of an in-the-money or at-the-money derivative security.
+    InTheMoneyExercise,
+    /// `G`: bona fide gift.
+    Gift,
+    /// `L`: small acquisition under Rule 16a-6.
+    SmallAcquisition,
+    /// `W`: acquisition or disposition by will or the laws of descent and distribution.
+    Inheritance,
+    /// `Z`: deposit into or withdrawal from a voting trust.
+    VotingTrust,
+    /// `J`: other acquisition or disposition, described in a footnote.
+    Other,
+    /// `K`: transaction in an equity swap or similar instrument.
+    EquitySwap,
+    /// `U`: disposition following a tender of shares in a change of control.
+    ChangeOfControlTender,
+    /// Any code not listed above, as written in the document.
+    Unknown(String),
+}
+impl TransactionCode {
+    /// The letter of the code, such as `S`.
+    pub fn code(&self) -> &str {
+        match self {
+            Self::OpenMarketPurchase => "P",
+            Self::OpenMarketSale => "S",
+            Self::VoluntarilyReported => "V",
+            Self::Grant => "A",
+            Self::DispositionToIssuer => "D",
+            Self::TaxWithholding => "F",
+            Self::Discretionary => "I",
+            Self::ExemptExercise => "M",
+            Self::Conversion => "C",
+            Self::ShortExpiration => "E",
+            Self::LongExpiration => "H",
+            Self::OutOfTheMoneyExercise => "O",
+            Self::InTheMoneyExercise => "X",
+            Self::Gift => "G",
+            Self::SmallAcquisition => "L",
+            Self::Inheritance => "W",
+            Self::VotingTrust => "Z",
+            Self::Other => "J",
+            Self::EquitySwap => "K",
+            Self::ChangeOfControlTender => "U",
+            Self::Unknown(code) => code,
+        }
+    }
+}
+impl From<&str> for TransactionCode {
+    fn from(code: &str) -> Self {
+        match code.trim() {
+            "P" => Self::OpenMarketPurchase,
+            "S" => Self::OpenMarketSale,
+            "V" => Self::VoluntarilyReported,
+            "A" => Self::Grant,
+            "D" => Self::DispositionToIssuer,
+            "F" => Self::TaxWithholding,
+            "I" => Self::Discretionary,
+            "M" => Self::ExemptExercise,
+            "C" => Self::Conversion,
+            "E" => Self::ShortExpiration,
+            "H" => Self::LongExpiration,
+            "O" => Self::OutOfTheMoneyExercise,
+            "X" => Self::InTheMoneyExercise,
+            "G" => Self::Gift,
+            "L" => Self::SmallAcquisition,
+            "W" => Self::Inheritance,
+            "Z" => Self::VotingTrust,
+            "J" => Self::Other,
+            "K" => Self::EquitySwap,
+            "U" => Self::ChangeOfControlTender,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+/// Whether the securities of a transaction were acquired or disposed of.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AcquiredDisposed {
+    /// `A`
+    Acquired,
+    /// `D`
+    Disposed,
+}
+impl FromStr for AcquiredDisposed {
+    type Err = EDGARError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Self::Acquired),
+            "D" => Ok(Self::Disposed),
+            _ => Err(invalid_field("acquired or disposed code", s)),
+        }
+    }
+}
+
+/// Whether the reporting owner holds the securities directly or through someone else.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DirectOrIndirect {
+    /// `D`
+    Direct,
+    /// `I`, such as through a trust or a family member.
+    Indirect,
+}
+impl FromStr for DirectOrIndirect {
+    type Err = EDGARError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "D" => Ok(Self::Direct),
+            "I" => Ok(Self::Indirect),
+            _ => Err(invalid_field("direct or indirect ownership", s)),
+        }
+    }
+}
+
+/// The company whose securities the document is about.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Issuer {
+    /// The CIK with leading zeros.
+    pub cik: String,
+    /// The name of the issuer.
+    pub name: String,
+    /// The ticker of the issuer, such as `AAPL`.
+    pub trading_symbol: Option<String>,
+}
+
+/// How a reporting owner is related to the issuer. An owner may have several relationships.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Relationship {
+    /// A member of the board of directors.
+    pub is_director: bool,
+    /// An officer, see `officer_title`.
+    pub is_officer: bool,
+    /// The beneficial owner of more than ten percent of a class of securities.
+    pub is_ten_percent_owner: bool,
+    /// Any other relationship, see `other_text`.
+    pub is_other: bool,
+    /// The title of an officer, such as `Chief Executive Officer`.
+    pub officer_title: Option<String>,
+    /// The description of another relationship.
+    pub other_text: Option<String>,
+}
+
+/// An insider the document reports for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportingOwner {
+    /// The CIK with leading zeros.
+    pub cik: String,
+    /// The name of the owner.
+    pub name: Option<String>,
+    /// The first line of the street address.
+    pub street1: Option<String>,
+    /// The second line of the street address.
+    pub street2: Option<String>,
+    /// The city.
+    pub city: Option<String>,
+    /// The state code.
+    pub state: Option<String>,
+    /// The ZIP code.
+    pub zip: Option<String>,
+    /// See [Relationship]
+    pub relationship: Relationship,
+}
+
+/// How a transaction was reported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionCoding {
+    /// The form the transaction is reported on, such as `4`.
+    pub form_type: Option<String>,
+    /// See [TransactionCode]
+    pub code: TransactionCode,
+    /// Whether the transaction involved an equity swap.
+    pub equity_swap_involved: bool,
+    /// The text of the footnotes referenced by the coding.
+    pub footnotes: Vec<String>,
+}
+
+/// The direct or indirect ownership of securities.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OwnershipNature {
+    /// See [DirectOrIndirect]
+    pub direct_or_indirect: Footnoted<DirectOrIndirect>,
+    /// How securities are owned indirectly, such as `By Trust`.
+    pub nature_of_ownership: Footnoted<String>,
+}
+
+/// The terms of a derivative security, such as an option or a restricted stock unit.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DerivativeTerms {
+    /// The price at which the security is exercised or converted.
+    pub conversion_or_exercise_price: Footnoted<f64>,
+    /// The date from which the security can be exercised.
+    pub exercise_date: Footnoted<NaiveDate>,
+    /// The date at which the security expires.
+    pub expiration_date: Footnoted<NaiveDate>,
+    /// The title of the underlying security, such as `Common Stock`.
+    pub underlying_security_title: Footnoted<String>,
+    /// The number of shares of the underlying security.
+    pub underlying_security_shares: Footnoted<f64>,
+    /// The value of the underlying security, for securities not counted in shares.
+    pub underlying_security_value: Footnoted<f64>,
+}
+
+/// A row of the non-derivative or derivative table that reports a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transaction {
+    /// The title of the security, such as `Common Stock`.
+    pub security_title: Footnoted<String>,
+    /// The date of the transaction.
+    pub transaction_date: Footnoted<NaiveDate>,
+    /// The date of execution, for transactions under a plan that sets it later than the transaction date.
+    pub deemed_execution_date: Footnoted<NaiveDate>,
+    /// See [TransactionCoding]
+    pub coding: Option<TransactionCoding>,
+    /// The number of shares.
+    pub shares: Footnoted<f64>,
+    /// The price per share.
+    pub price_per_share: Footnoted<f64>,
+    /// See [AcquiredDisposed]
+    pub acquired_disposed: Footnoted<AcquiredDisposed>,
+    /// The number of shares owned after the transaction.
+    pub shares_owned_following: Footnoted<f64>,
+    /// The value owned after the transaction, for securities not counted in shares.
+    pub value_owned_following: Footnoted<f64>,
+    /// See [OwnershipNature]
+    pub ownership_nature: OwnershipNature,
+    /// See [DerivativeTerms]. None in the non-derivative table.
+    pub derivative: Option<DerivativeTerms>,
+}
+impl Transaction {
+    /// The [TransactionCode] of the transaction.
+    pub fn code(&self) -> Option<&TransactionCode> {
+        self.coding.as_ref().map(|c| &c.code)
+    }
+}
+
+/// A row of the non-derivative or derivative table that reports a holding, without a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holding {
+    /// The title of the security, such as `Common Stock`.
+    pub security_title: Footnoted<String>,
+    /// The number of shares owned.
+    pub shares_owned_following: Footnoted<f64>,
+    /// The value owned, for securities not counted in shares.
+    pub value_owned_following: Footnoted<f64>,
+    /// See [OwnershipNature]
+    pub ownership_nature: OwnershipNature,
+    /// See [DerivativeTerms]. None in the non-derivative table.
+    pub derivative: Option<DerivativeTerms>,
+}
+
+/// The signature of a reporting owner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    /// The name of the signer.
+    pub name: String,
+    /// The date of the signature.
+    pub date: Option<NaiveDate>,
+}
+
+/// An ownership document, the XML of a Form 3, 4 or 5.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnershipDocument {
+    /// The version of the schema, such as `X0508`.
+    pub schema_version: Option<String>,
+    /// The form, such as `4` or `4/A`.
+    pub document_type: String,
+    /// The date of the earliest transaction reported.
+    pub period_of_report: Option<NaiveDate>,
+    /// Whether the owner is no longer subject to Section 16, for Forms 4 and 5.
+    pub not_subject_to_section16: bool,
+    /// Whether a transaction was made under a Rule 10b5-1(c) trading plan.
+    pub aff_10b5_one: bool,
+    /// See [Issuer]
+    pub issuer: Issuer,
+    /// See [ReportingOwner]
+    pub reporting_owners: Vec<ReportingOwner>,
+    /// See [Transaction]
+    pub non_derivative_transactions: Vec<Transaction>,
+    /// See [Holding]
+    pub non_derivative_holdings: Vec<Holding>,
+    /// See [Transaction]
+    pub derivative_transactions: Vec<Transaction>,
+    /// See [Holding]
+    pub derivative_holdings: Vec<Holding>,
+    /// The footnotes of the document by id, such as `F1`.
+    pub footnotes: BTreeMap<String, String>,
+    /// The remarks of the filer.
+    pub remarks: Option<String>,
+    /// See [Signature]
+    pub signatures: Vec<Signature>,
+}
+impl OwnershipDocument {
+    /// Parses the XML of an ownership document.
+    pub fn new(xml: &str) -> Result<Self, EDGARError> {
+        let raw: RawOwnershipDocument = from_str(xml.trim())?;
+        let footnotes: BTreeMap<String, String> = raw
+            .footnotes
+            .map(|f| f.footnote)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|f| (f.id, f.text.trim().to_string()))
+            .collect();
+        let resolver = Resolver {
+            footnotes: &footnotes,
+        };
+        let mut document = Self {
+            schema_version: non_empty(raw.schema_version),
+            document_type: raw.document_type.trim().to_string(),
+            period_of_report: non_empty(raw.period_of_report)
+                .map(|d| date(&d))
+                .transpose()?,
+            not_subject_to_section16: flag(raw.not_subject_to_section16),
+            aff_10b5_one: flag(raw.aff10b5_one),
+            issuer: Issuer {
+                cik: add_leading_zeros_to_cik(raw.issuer.issuer_cik.trim()),
+                name: raw.issuer.issuer_name.trim().to_string(),
+                trading_symbol: non_empty(raw.issuer.issuer_trading_symbol),
+            },
+            reporting_owners: raw
+                .reporting_owner
+                .into_iter()
+                .map(ReportingOwner::from)
+                .collect(),
+            non_derivative_transactions: vec![],
+            non_derivative_holdings: vec![],
+            derivative_transactions: vec![],
+            derivative_holdings: vec![],
+            footnotes: BTreeMap::new(),
+            remarks: non_empty(raw.remarks),
+            signatures: raw
+                .owner_signature
+                .into_iter()
+                .map(|s| {
+                    Ok(Signature {
+                        name: s.signature_name.trim().to_string(),
+                        date: non_empty(s.signature_date).map(|d| date(&d)).transpose()?,
+                    })
+                })
+                .collect::<Result<_, EDGARError>>()?,
+        };
+        for row in raw.non_derivative_table.map(|t| t.rows).unwrap_or_default() {
+            match row {
+                RawRow::NonDerivativeTransaction(t) => document
+                    .non_derivative_transactions
+                    .push(resolver.transaction(t, false)?),
+                RawRow::NonDerivativeHolding(h) => document
+                    .non_derivative_holdings
+                    .push(resolver.holding(h, false)?),
+                _ => {}
+            }
+        }
+        for row in raw.derivative_table.map(|t| t.rows).unwrap_or_default() {
+            match row {
+                RawRow::DerivativeTransaction(t) => document
+                    .derivative_transactions
+                    .push(resolver.transaction(t, true)?),
+                RawRow::DerivativeHolding(h) => document
+                    .derivative_holdings
+                    .push(resolver.holding(h, true)?),
+                _ => {}
+            }
+        }
+        document.footnotes = footnotes;
+        Ok(document)
+    }
+}
+
+/// Get the ownership document of the filing `accession_number` of the company with `cik`,
+/// read from the complete submission text file of the filing.
+pub async fn get_ownership_document(
+    client: EdgarClient,
+    cik: &str,
+    accession_number: &AccessionNumber,
+) -> Result<OwnershipDocument, EDGARError> {
+    let mut reader = get_complete_submission(client, cik, accession_number).await?;
+    while let Some(document) = reader.next_document().await? {
+        let text = document.text();
+        if text.contains("<ownershipDocument") {
+            return OwnershipDocument::new(&text);
+        }
+    }
+    Err(EDGARError::OwnershipDocumentNotFound)
+}
+
+/// Resolves the footnotes referenced by the values of a document.
+struct Resolver<'a> {
+    footnotes: &'a BTreeMap<String, String>,
+}
+impl Resolver<'_> {
+    fn footnotes<'b>(&self, ids: impl IntoIterator<Item = &'b str>) -> Vec<String> {
+        ids.into_iter()
+            .filter_map(|id| self.footnotes.get(id).cloned())
+            .collect()
+    }
+    fn value<T, F>(&self, raw: Option<RawValue>, parse: F) -> Result<Footnoted<T>, EDGARError>
+    where
+        F: Fn(&str) -> Result<T, EDGARError>,
+    {
+        let children = raw.map(|r| r.children).unwrap_or_default();
+        let value = children.iter().find_map(|c| match c {
+            RawValueChild::Value(value) => non_empty(Some(value.clone())),
+            _ => None,
+        });
+        Ok(Footnoted {
+            value: value.map(|v| parse(&v)).transpose()?,
+            footnotes: self.footnotes(children.iter().filter_map(|c| match c {
+                RawValueChild::FootnoteId { id } => Some(id.as_str()),
+                _ => None,
+            })),
+        })
+    }
+    fn text(&self, raw: Option<RawValue>) -> Result<Footnoted<String>, EDGARError> {
+        self.value(raw, |v| Ok(v.to_string()))
+    }
+    fn number(&self, raw: Option<RawValue>, field: &str) -> Result<Footnoted<f64>, EDGARError> {
+        // Some filers write thousands separators, such as `1,000`.
+        self.value(raw, |v| {
+            v.replace(',', "")
+                .parse()
+                .map_err(|_| invalid_field(field, v))
+        })
+    }
+    fn date(&self, raw: Option<RawValue>) -> Result<Footnoted<NaiveDate>, EDGARError> {
+        self.value(raw, date)
+    }
+    fn ownership_nature(
+        &self,
+        raw: Option<RawOwnershipNature>,
+    ) -> Result<OwnershipNature, EDGARError> {
+        let raw = raw.unwrap_or_default();
+        Ok(OwnershipNature {
+            direct_or_indirect: self.value(raw.direct_or_indirect_ownership, str::parse)?,
+            nature_of_ownership: self.text(raw.nature_of_ownership)?,
+        })
+    }
+    fn derivative(&self, row: &mut RawTableRow) -> Result<DerivativeTerms, EDGARError> {
+        let underlying = row.underlying_security.take().unwrap_or_default();
+        Ok(DerivativeTerms {
+            conversion_or_exercise_price: self.number(
+                row.conversion_or_exercise_price.take(),
+                "conversion or exercise price",
+            )?,
+            exercise_date: self.date(row.exercise_date.take())?,
+            expiration_date: self.date(row.expiration_date.take())?,
+            underlying_security_title: self.text(underlying.underlying_security_title)?,
+            underlying_security_shares: self.number(
+                underlying.underlying_security_shares,
+                "underlying security shares",
+            )?,
+            underlying_security_value: self.number(
+                underlying.underlying_security_value,
+                "underlying security value",
+            )?,
+        })
+    }
+    fn transaction(
+        &self,
+        mut row: RawTableRow,
+        derivative: bool,
+    ) -> Result<Transaction, EDGARError> {
+        let derivative = match derivative {
+            true => Some(self.derivative(&mut row)?),
+            false => None,
+        };
+        let amounts = row.transaction_amounts.unwrap_or_default();
+        let post = row.post_transaction_amounts.unwrap_or_default();
+        Ok(Transaction {
+            security_title: self.text(row.security_title)?,
+            transaction_date: self.date(row.transaction_date)?,
+            deemed_execution_date: self.date(row.deemed_execution_date)?,
+            coding: row.transaction_coding.map(|c| TransactionCoding {
+                form_type: non_empty(c.transaction_form_type),
+                code: c.transaction_code.as_str().into(),
+                equity_swap_involved: flag(c.equity_swap_involved),
+                footnotes: self.footnotes(c.footnote_id.iter().map(|f| f.id.as_str())),
+            }),
+            shares: self.number(amounts.transaction_shares, "transaction shares")?,
+            price_per_share: self.number(amounts.transaction_price_per_share, "price per share")?,
+            acquired_disposed: self
+                .value(amounts.transaction_acquired_disposed_code, str::parse)?,
+            shares_owned_following: self.number(
+                post.shares_owned_following_transaction,
+                "shares owned following transaction",
+            )?,
+            value_owned_following: self.number(
+                post.value_owned_following_transaction,
+                "value owned following transaction",
+            )?,
+            ownership_nature: self.ownership_nature(row.ownership_nature)?,
+            derivative,
+        })
+    }
+    fn holding(&self, mut row: RawTableRow, derivative: bool) -> Result<Holding, EDGARError> {
+        let derivative = match derivative {
+            true => Some(self.derivative(&mut row)?),
+            false => None,
+        };
+        let post = row.post_transaction_amounts.unwrap_or_default();
+        Ok(Holding {
+            security_title: self.text(row.security_title)?,
+            shares_owned_following: self.number(
+                post.shares_owned_following_transaction,
+                "shares owned following transaction",
+            )?,
+            value_owned_following: self.number(
+                post.value_owned_following_transaction,
+                "value owned following transaction",
+            )?,
+            ownership_nature: self.ownership_nature(row.ownership_nature)?,
+            derivative,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOwnershipDocument {
+    schema_version: Option<String>,
+    document_type: String,
+    period_of_report: Option<String>,
+    not_subject_to_section16: Option<String>,
+    issuer: RawIssuer,
+    #[serde(default)]
+    reporting_owner: Vec<RawReportingOwner>,
+    #[serde(rename = "aff10b5One")]
+    aff10b5_one: Option<String>,
+    non_derivative_table: Option<RawTable>,
+    derivative_table: Option<RawTable>,
+    footnotes: Option<RawFootnotes>,
+    remarks: Option<String>,
+    #[serde(default)]
+    owner_signature: Vec<RawSignature>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawIssuer {
+    issuer_cik: String,
+    issuer_name: String,
+    issuer_trading_symbol: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawReportingOwner {
+    reporting_owner_id: RawReportingOwnerId,
+    reporting_owner_address: Option<RawReportingOwnerAddress>,
+    reporting_owner_relationship: Option<RawRelationship>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawReportingOwnerId {
+    rpt_owner_cik: String,
+    rpt_owner_name: Option<String>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawReportingOwnerAddress {
+    rpt_owner_street1: Option<String>,
+    rpt_owner_street2: Option<String>,
+    rpt_owner_city: Option<String>,
+    rpt_owner_state: Option<String>,
+    rpt_owner_zip_code: Option<String>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRelationship {
+    is_director: Option<String>,
+    is_officer: Option<String>,
+    is_ten_percent_owner: Option<String>,
+    is_other: Option<String>,
+    officer_title: Option<String>,
+    other_text: Option<String>,
+}
+impl From<RawReportingOwner> for ReportingOwner {
+    fn from(raw: RawReportingOwner) -> Self {
+        let address = raw.reporting_owner_address.unwrap_or_default();
+        let relationship = raw.reporting_owner_relationship.unwrap_or_default();
+        Self {
+            cik: add_leading_zeros_to_cik(raw.reporting_owner_id.rpt_owner_cik.trim()),
+            name: non_empty(raw.reporting_owner_id.rpt_owner_name),
+            street1: non_empty(address.rpt_owner_street1),
+            street2: non_empty(address.rpt_owner_street2),
+            city: non_empty(address.rpt_owner_city),
+            state: non_empty(address.rpt_owner_state),
+            zip: non_empty(address.rpt_owner_zip_code),
+            relationship: Relationship {
+                is_director: flag(relationship.is_director),
+                is_officer: flag(relationship.is_officer),
+                is_ten_percent_owner: flag(relationship.is_ten_percent_owner),
+                is_other: flag(relationship.is_other),
+                officer_title: non_empty(relationship.officer_title),
+                other_text: non_empty(relationship.other_text),
+            },
+        }
+    }
+}
+/// A table whose transactions and holdings may be interleaved.
+#[derive(Debug, Deserialize)]
+struct RawTable {
+    #[serde(rename = "$value", default)]
+    rows: Vec<RawRow>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawRow {
+    NonDerivativeTransaction(RawTableRow),
+    NonDerivativeHolding(RawTableRow),
+    DerivativeTransaction(RawTableRow),
+    DerivativeHolding(RawTableRow),
+    #[serde(other)]
+    Other,
+}
+/// The elements of every kind of row, most of which only some kinds have.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTableRow {
+    security_title: Option<RawValue>,
+    conversion_or_exercise_price: Option<RawValue>,
+    transaction_date: Option<RawValue>,
+    deemed_execution_date: Option<RawValue>,
+    transaction_coding: Option<RawTransactionCoding>,
+    transaction_amounts: Option<RawTransactionAmounts>,
+    exercise_date: Option<RawValue>,
+    expiration_date: Option<RawValue>,
+    underlying_security: Option<RawUnderlyingSecurity>,
+    post_transaction_amounts: Option<RawPostTransactionAmounts>,
+    ownership_nature: Option<RawOwnershipNature>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTransactionCoding {
+    transaction_form_type: Option<String>,
+    #[serde(default)]
+    transaction_code: String,
+    equity_swap_involved: Option<String>,
+    #[serde(default)]
+    footnote_id: Vec<RawFootnoteId>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTransactionAmounts {
+    transaction_shares: Option<RawValue>,
+    transaction_price_per_share: Option<RawValue>,
+    transaction_acquired_disposed_code: Option<RawValue>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawUnderlyingSecurity {
+    underlying_security_title: Option<RawValue>,
+    underlying_security_shares: Option<RawValue>,
+    underlying_security_value: Option<RawValue>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPostTransactionAmounts {
+    shares_owned_following_transaction: Option<RawValue>,
+    value_owned_following_transaction: Option<RawValue>,
+}
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOwnershipNature {
+    direct_or_indirect_ownership: Option<RawValue>,
+    nature_of_ownership: Option<RawValue>,
+}
+/// An element holding a `<value>` and the `<footnoteId>`s that qualify it.
+///
+/// The children are read in order because serde-xml-rs fails on a sequence of `<footnoteId>`
+/// that ends a row followed by another row.
+#[derive(Debug, Deserialize)]
+struct RawValue {
+    #[serde(rename = "$value", default)]
+    children: Vec<RawValueChild>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawValueChild {
+    Value(String),
+    FootnoteId {
+        id: String,
+    },
+    #[serde(other)]
+    Other,
+}
+#[derive(Debug, Deserialize)]
+struct RawFootnoteId {
+    id: String,
+}
+#[derive(Debug, Deserialize)]
+struct RawFootnotes {
+    #[serde(default)]
+    footnote: Vec<RawFootnote>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFootnote {
+    id: String,
+    #[serde(rename = "$value", default)]
+    text: String,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSignature {
+    signature_name: String,
+    signature_date: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, xml_submission, TestResponse, TestServer};
+
+    fn form4() -> OwnershipDocument {
+        OwnershipDocument::new(include_str!("../tests/fixtures/ownership/form4.xml")).unwrap()
+    }
+    #[test]
+    fn ownership_issuer_and_owners() {
+        let form4 = form4();
+        assert_eq!(form4.document_type, "4");
+        assert_eq!(form4.period_of_report, NaiveDate::from_ymd_opt(2023, 8, 1));
+        assert!(!form4.not_subject_to_section16);
+        assert!(form4.aff_10b5_one);
+        assert_eq!(form4.issuer.cik, "0000320193");
+        assert_eq!(form4.issuer.trading_symbol.as_deref(), Some("AAPL"));
+        assert_eq!(form4.reporting_owners.len(), 2);
+        let levinson = &form4.reporting_owners[0];
+        assert_eq!(levinson.cik, "0001214128");
+        assert_eq!(levinson.city.as_deref(), Some("CUPERTINO"));
+        assert_eq!(levinson.street2, None);
+        assert!(levinson.relationship.is_director);
+        assert!(!levinson.relationship.is_officer);
+        let obrien = &form4.reporting_owners[1].relationship;
+        assert!(obrien.is_officer);
+        assert_eq!(
+            obrien.officer_title.as_deref(),
+            Some("Senior Vice President")
+        );
+        assert_eq!(
+            form4.signatures[0].date,
+            NaiveDate::from_ymd_opt(2023, 8, 3)
+        );
+        assert_eq!(form4.footnotes.len(), 4)
+    }
+    #[test]
+    fn ownership_non_derivative_table() {
+        let form4 = form4();
+        assert_eq!(form4.non_derivative_transactions.len(), 2);
+        assert_eq!(form4.non_derivative_holdings.len(), 1);
+        let sale = &form4.non_derivative_transactions[0];
+        assert_eq!(sale.code(), Some(&TransactionCode::OpenMarketSale));
+        assert_eq!(sale.shares.value, Some(1000.0));
+        assert_eq!(sale.price_per_share.value, Some(195.5));
+        assert_eq!(sale.price_per_share.footnotes.len(), 2);
+        assert!(sale.price_per_share.footnotes[1].contains("vesting & settlement"));
+        assert_eq!(
+            sale.acquired_disposed.value,
+            Some(AcquiredDisposed::Disposed)
+        );
+        assert_eq!(sale.shares_owned_following.value, Some(4000.0));
+        let xml = include_str!("../tests/fixtures/ownership/form4.xml").replacen(
+            "<value>1000</value>",
+            "<value>1,000</value>",
+            1,
+        );
+        let separated = OwnershipDocument::new(&xml).unwrap();
+        assert_eq!(
+            separated.non_derivative_transactions[0].shares.value,
+            Some(1000.0)
+        );
+        assert_eq!(sale.deemed_execution_date.value, None);
+        assert!(sale.coding.as_ref().unwrap().footnotes[0].contains("10b5-1"));
+        assert!(sale.derivative.is_none());
+        let exercise = &form4.non_derivative_transactions[1];
+        assert_eq!(exercise.code().unwrap().code(), "M");
+        assert!(!exercise.coding.as_ref().unwrap().equity_swap_involved);
+        assert_eq!(exercise.price_per_share.value, None);
+        assert_eq!(exercise.price_per_share.footnotes.len(), 1);
+        let trust = &form4.non_derivative_holdings[0];
+        assert_eq!(
+            trust.ownership_nature.direct_or_indirect.value,
+            Some(DirectOrIndirect::Indirect)
+        );
+        assert_eq!(
+            trust.ownership_nature.nature_of_ownership.value.as_deref(),
+            Some("By Trust")
+        )
+    }
+    #[test]
+    fn ownership_derivative_table() {
+        let form4 = form4();
+        assert!(form4.derivative_holdings.is_empty());
+        let rsu = &form4.derivative_transactions[0];
+        assert_eq!(rsu.code(), Some(&TransactionCode::ExemptExercise));
+        let terms = rsu.derivative.as_ref().unwrap();
+        assert_eq!(terms.conversion_or_exercise_price.value, None);
+        assert!(terms.expiration_date.footnotes[0].starts_with("Each restricted stock unit"));
+        assert_eq!(
+            terms.underlying_security_title.value.as_deref(),
+            Some("Common Stock")
+        );
+        assert_eq!(terms.underlying_security_shares.value, Some(500.0));
+        assert_eq!(rsu.shares_owned_following.value, Some(1500.0))
+    }
+    #[tokio::test]
+    async fn ownership_get_ownership_document() {
+        let xml = include_str!("../tests/fixtures/ownership/form4.xml");
+        let submission = xml_submission("0001214128-23-000005", "4", "20230803", &[("4", xml)]);
+        let server = TestServer::start().route(
+            "/Archives/edgar/data/320193/000121412823000005/0001214128-23-000005.txt",
+            vec![TestResponse::ok("text/plain", &submission)],
+        );
+        let client = client(&server);
+        let accession_number = "0001214128-23-000005".parse().unwrap();
+        let form4 = get_ownership_document(client, "0000320193", &accession_number)
+            .await
+            .unwrap();
+        assert_eq!(form4, self::form4())
+    }
+    #[test]
+    fn ownership_transaction_codes() {
+        for code in ["P", "S", "A", "D", "F", "M", "G", "J", "X"] {
+            assert_eq!(TransactionCode::from(code).code(), code)
+        }
+        assert_eq!(
+            TransactionCode::from("Q"),
+            TransactionCode::Unknown("Q".to_string())
+        );
+        assert!("B".parse::<AcquiredDisposed>().is_err())
+    }
+}
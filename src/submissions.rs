@@ -0,0 +1,359 @@
+//! This module provides the submissions API of `data.sec.gov`, which holds the details of an entity and its filing history.
+//!
+//! The API serves the most recent filings of an entity, at least one year's worth or 1,000 filings, as columnar arrays.
+//! Older filings are split into additional JSON files listed under `filings.files`.
+//! [get_submissions] follows those files and turns every column into rows of [SubmissionsFiling].
+//! ```
+//! use sec_edgar::{edgar::edgar_client, submissions::get_submissions};
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let submissions = get_submissions(client, "831001").await.unwrap();
+//!     println!("{} has {} filings", submissions.name, submissions.filings.len());
+//! }
+//! ```
+
+use crate::client::EdgarClient;
+use crate::edgar_query::{
+    company_info::{Address, AddressType, FiscalYearEnd},
+    edgar_query_builder::add_leading_zeros_to_cik,
+    filing_content_value::AccessionNumber,
+};
+use crate::error::EDGARError;
+use crate::fields::{date, invalid_field, non_empty};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::Deserialize;
+
+/// The details of an entity and every filing it submitted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submissions {
+    /// The CIK with leading zeros.
+    pub cik: String,
+    /// The name of the entity.
+    pub name: String,
+    /// The kind of entity, such as `operating` or `other`.
+    pub entity_type: Option<String>,
+    /// The Standard Industrial Classification code.
+    pub sic: Option<u16>,
+    /// The description of the Standard Industrial Classification code.
+    pub sic_description: Option<String>,
+    /// The tickers of the entity's securities.
+    pub tickers: Vec<String>,
+    /// The exchange of each ticker, in the same order.
+    pub exchanges: Vec<Option<String>>,
+    /// The Employer Identification Number.
+    pub ein: Option<String>,
+    /// The filer category, such as `Large accelerated filer`.
+    pub category: Option<String>,
+    /// See [FiscalYearEnd]
+    pub fiscal_year_end: Option<FiscalYearEnd>,
+    /// The state where the entity is incorporated.
+    pub state_of_incorporation: Option<String>,
+    /// The phone number.
+    pub phone: Option<String>,
+    /// The business and mailing addresses. See [Address]
+    pub addresses: Vec<Address>,
+    /// See [SubmissionsFormerName]
+    pub former_names: Vec<SubmissionsFormerName>,
+    /// Every filing, from the most recent to the oldest.
+    pub filings: Vec<SubmissionsFiling>,
+    /// The additional files that held the older filings. See [SubmissionsFile]
+    pub files: Vec<SubmissionsFile>,
+}
+/// A name the entity was previously known by.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmissionsFormerName {
+    /// The former name.
+    pub name: String,
+    /// When the entity started using the name.
+    pub from: Option<NaiveDate>,
+    /// When the entity stopped using the name.
+    pub to: Option<NaiveDate>,
+}
+/// A filing of the entity, one row of the columnar arrays of the API.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmissionsFiling {
+    /// See [AccessionNumber]
+    pub accession_number: AccessionNumber,
+    /// The date at which the filing was made.
+    pub filing_date: NaiveDate,
+    /// The end of the period the filing reports on, if any.
+    pub report_date: Option<NaiveDate>,
+    /// When EDGAR accepted the filing.
+    pub acceptance_date_time: Option<DateTime<FixedOffset>>,
+    /// The act under which the filing was made, such as `34`.
+    pub act: String,
+    /// The form type, such as `10-Q` or `SC 13G/A`.
+    pub form: String,
+    /// The file number.
+    pub file_number: String,
+    /// The film number.
+    pub film_number: String,
+    /// The items reported, for forms such as 8-K.
+    pub items: String,
+    /// The size of the filing in bytes.
+    pub size: u64,
+    /// Whether the filing includes XBRL financial data.
+    pub is_xbrl: bool,
+    /// Whether the filing includes inline XBRL.
+    pub is_inline_xbrl: bool,
+    /// The file name of the primary document in the archives.
+    pub primary_document: String,
+    /// The description of the primary document.
+    pub primary_doc_description: String,
+}
+/// An additional file that holds older filings of the entity.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionsFile {
+    /// The file name, such as `CIK0000831001-submissions-001.json`.
+    pub name: String,
+    /// The number of filings in the file.
+    pub filing_count: u32,
+    /// The date of the oldest filing in the file.
+    pub filing_from: NaiveDate,
+    /// The date of the most recent filing in the file.
+    pub filing_to: NaiveDate,
+}
+
+/// Get the details and every filing of the entity with `cik`, following the additional files of older filings.
+/// Leading zeros are added to the CIK if needed.
+pub async fn get_submissions(client: EdgarClient, cik: &str) -> Result<Submissions, EDGARError> {
+    let cik = add_leading_zeros_to_cik(cik);
+    let url = client
+        .endpoints()
+        .data_url(&format!("submissions/CIK{cik}.json"))?;
+    let raw: RawSubmissions = client.get_json(&url).await?;
+    let mut filings = raw.filings.recent.rows()?;
+    for file in &raw.filings.files {
+        let url = client
+            .endpoints()
+            .data_url(&format!("submissions/{}", file.name))?;
+        let older: RawFilings = client.get_json(&url).await?;
+        filings.extend(older.rows()?);
+    }
+    Ok(Submissions {
+        cik,
+        name: raw.name,
+        entity_type: non_empty(raw.entity_type),
+        sic: raw.sic.and_then(|s| s.trim().parse().ok()),
+        sic_description: non_empty(raw.sic_description),
+        tickers: raw.tickers,
+        exchanges: raw.exchanges,
+        ein: non_empty(raw.ein),
+        category: non_empty(raw.category),
+        fiscal_year_end: raw.fiscal_year_end.as_deref().and_then(|f| f.parse().ok()),
+        state_of_incorporation: non_empty(raw.state_of_incorporation),
+        phone: non_empty(raw.phone),
+        addresses: raw.addresses.into_addresses(),
+        former_names: raw
+            .former_names
+            .into_iter()
+            .map(|f| SubmissionsFormerName {
+                name: f.name,
+                from: f.from.as_deref().and_then(|d| date(d).ok()),
+                to: f.to.as_deref().and_then(|d| date(d).ok()),
+            })
+            .collect(),
+        filings,
+        files: raw.filings.files,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSubmissions {
+    name: String,
+    entity_type: Option<String>,
+    sic: Option<String>,
+    sic_description: Option<String>,
+    #[serde(default)]
+    tickers: Vec<String>,
+    #[serde(default)]
+    exchanges: Vec<Option<String>>,
+    ein: Option<String>,
+    category: Option<String>,
+    fiscal_year_end: Option<String>,
+    state_of_incorporation: Option<String>,
+    phone: Option<String>,
+    #[serde(default)]
+    addresses: RawAddresses,
+    #[serde(default)]
+    former_names: Vec<RawFormerName>,
+    filings: RawFilingsIndex,
+}
+#[derive(Debug, Default, Deserialize)]
+struct RawAddresses {
+    mailing: Option<RawAddress>,
+    business: Option<RawAddress>,
+}
+impl RawAddresses {
+    fn into_addresses(self) -> Vec<Address> {
+        [
+            (AddressType::Mailing, self.mailing),
+            (AddressType::Business, self.business),
+        ]
+        .into_iter()
+        .filter_map(|(address_type, a)| {
+            let a = a?;
+            Some(Address {
+                address_type,
+                street1: non_empty(a.street1),
+                street2: non_empty(a.street2),
+                city: non_empty(a.city),
+                state: non_empty(a.state_or_country),
+                zip: non_empty(a.zip_code),
+                phone: None,
+            })
+        })
+        .collect()
+    }
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAddress {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    zip_code: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFormerName {
+    name: String,
+    from: Option<String>,
+    to: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct RawFilingsIndex {
+    recent: RawFilings,
+    #[serde(default)]
+    files: Vec<SubmissionsFile>,
+}
+/// The columnar arrays of filings, as served in `filings.recent` and in the additional files.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFilings {
+    accession_number: Vec<String>,
+    filing_date: Vec<String>,
+    #[serde(default)]
+    report_date: Vec<String>,
+    #[serde(default)]
+    acceptance_date_time: Vec<String>,
+    #[serde(default)]
+    act: Vec<String>,
+    #[serde(default)]
+    form: Vec<String>,
+    #[serde(default)]
+    file_number: Vec<String>,
+    #[serde(default)]
+    film_number: Vec<String>,
+    #[serde(default)]
+    items: Vec<String>,
+    #[serde(default)]
+    size: Vec<u64>,
+    #[serde(default, rename = "isXBRL")]
+    is_xbrl: Vec<u8>,
+    #[serde(default, rename = "isInlineXBRL")]
+    is_inline_xbrl: Vec<u8>,
+    #[serde(default)]
+    primary_document: Vec<String>,
+    #[serde(default)]
+    primary_doc_description: Vec<String>,
+}
+impl RawFilings {
+    fn rows(&self) -> Result<Vec<SubmissionsFiling>, EDGARError> {
+        let text = |column: &[String], i: usize| column.get(i).cloned().unwrap_or_default();
+        let flag = |column: &[u8], i: usize| column.get(i).is_some_and(|f| *f != 0);
+        (0..self.accession_number.len())
+            .map(|i| {
+                let filing_date = text(&self.filing_date, i);
+                Ok(SubmissionsFiling {
+                    accession_number: self.accession_number[i].parse()?,
+                    filing_date: date(&filing_date)
+                        .map_err(|_| invalid_field("filing date", &filing_date))?,
+                    report_date: date(&text(&self.report_date, i)).ok(),
+                    acceptance_date_time: DateTime::parse_from_rfc3339(&text(
+                        &self.acceptance_date_time,
+                        i,
+                    ))
+                    .ok(),
+                    act: text(&self.act, i),
+                    form: text(&self.form, i),
+                    file_number: text(&self.file_number, i),
+                    film_number: text(&self.film_number, i),
+                    items: text(&self.items, i),
+                    size: self.size.get(i).copied().unwrap_or_default(),
+                    is_xbrl: flag(&self.is_xbrl, i),
+                    is_inline_xbrl: flag(&self.is_inline_xbrl, i),
+                    primary_document: text(&self.primary_document, i),
+                    primary_doc_description: text(&self.primary_doc_description, i),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+
+    fn server() -> TestServer {
+        TestServer::start()
+            .route(
+                "/submissions/CIK0000831001.json",
+                vec![TestResponse::ok(
+                    "application/json",
+                    include_bytes!("../tests/fixtures/submissions/CIK0000831001.json"),
+                )],
+            )
+            .route(
+                "/submissions/CIK0000831001-submissions-001.json",
+                vec![TestResponse::ok(
+                    "application/json",
+                    include_bytes!(
+                        "../tests/fixtures/submissions/CIK0000831001-submissions-001.json"
+                    ),
+                )],
+            )
+    }
+    #[tokio::test]
+    async fn submissions_entity_details() {
+        let server = server();
+        let submissions = get_submissions(client(&server), "831001").await.unwrap();
+        assert_eq!(submissions.cik, "0000831001");
+        assert_eq!(submissions.name, "CITIGROUP INC");
+        assert_eq!(submissions.sic, Some(6021));
+        assert_eq!(submissions.tickers, vec!["C", "C-PN"]);
+        assert_eq!(submissions.exchanges[0].as_deref(), Some("NYSE"));
+        assert_eq!(
+            submissions.fiscal_year_end,
+            Some(FiscalYearEnd { month: 12, day: 31 })
+        );
+        assert_eq!(submissions.addresses.len(), 2);
+        assert_eq!(submissions.addresses[1].address_type, AddressType::Business);
+        assert_eq!(
+            submissions.former_names[0].to,
+            NaiveDate::from_ymd_opt(1998, 10, 8)
+        )
+    }
+    #[tokio::test]
+    async fn submissions_filings_follow_files() {
+        let server = server();
+        let submissions = get_submissions(client(&server), "831001").await.unwrap();
+        let filings = &submissions.filings;
+        assert_eq!(filings.len(), 3);
+        assert_eq!(filings[0].accession_number.value, "0000831001-23-000114");
+        assert_eq!(filings[0].report_date, NaiveDate::from_ymd_opt(2023, 6, 30));
+        assert_eq!(filings[0].primary_document, "c-20230630.htm");
+        assert!(filings[0].is_inline_xbrl);
+        assert_eq!(filings[1].report_date, None);
+        assert!(!filings[1].is_inline_xbrl);
+        assert_eq!(filings[2].form, "SC 13G");
+        assert_eq!(
+            filings[2].filing_date,
+            NaiveDate::from_ymd_opt(1994, 2, 10).unwrap()
+        );
+        assert_eq!(server.requests().len(), 2)
+    }
+}
@@ -0,0 +1,154 @@
+//! This module provides the XBRL APIs of `data.sec.gov`, which serve the financial data tagged in filings.
+//!
+//! The types shared by the APIs, such as [Fact] and [Taxonomy], live here.
+pub mod company_concept;
+pub mod company_facts;
+pub mod frames;
+
+use crate::edgar_query::{
+    filing::{self, FilingTypeOption},
+    filing_content_value::AccessionNumber,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+use std::fmt::{self, Display};
+
+/// The taxonomy a concept is defined in.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(from = "String")]
+pub enum Taxonomy {
+    /// US Generally Accepted Accounting Principles, `us-gaap`.
+    UsGaap,
+    /// International Financial Reporting Standards, `ifrs-full`.
+    IfrsFull,
+    /// Document and Entity Information, `dei`.
+    Dei,
+    /// SEC Reporting Taxonomy, `srt`.
+    Srt,
+    /// Any other taxonomy, by name.
+    Other(String),
+}
+impl Taxonomy {
+    /// The name of the taxonomy, as used in the URLs and the JSON of the APIs.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::UsGaap => "us-gaap",
+            Self::IfrsFull => "ifrs-full",
+            Self::Dei => "dei",
+            Self::Srt => "srt",
+            Self::Other(name) => name,
+        }
+    }
+}
+impl From<&str> for Taxonomy {
+    fn from(name: &str) -> Self {
+        match name {
+            "us-gaap" => Self::UsGaap,
+            "ifrs-full" => Self::IfrsFull,
+            "dei" => Self::Dei,
+            "srt" => Self::Srt,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+impl From<String> for Taxonomy {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+impl Display for Taxonomy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The fiscal period a fact was reported for.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(from = "String")]
+pub enum FiscalPeriod {
+    /// The full fiscal year.
+    FY,
+    /// The first quarter.
+    Q1,
+    /// The second quarter.
+    Q2,
+    /// The third quarter.
+    Q3,
+    /// The fourth quarter.
+    Q4,
+    /// The first half of the fiscal year.
+    H1,
+    /// The second half of the fiscal year.
+    H2,
+    /// Any other fiscal period, as written in the API.
+    Other(String),
+}
+impl From<String> for FiscalPeriod {
+    fn from(fp: String) -> Self {
+        match fp.as_str() {
+            "FY" => Self::FY,
+            "Q1" => Self::Q1,
+            "Q2" => Self::Q2,
+            "Q3" => Self::Q3,
+            "Q4" => Self::Q4,
+            "H1" => Self::H1,
+            "H2" => Self::H2,
+            _ => Self::Other(fp),
+        }
+    }
+}
+
+/// A value reported in a filing for a concept, in one unit.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Fact {
+    /// The start of the period the value covers. None for values at an instant, such as balances.
+    pub start: Option<NaiveDate>,
+    /// The end of the period, or the instant, the value covers.
+    pub end: NaiveDate,
+    /// The value.
+    pub val: f64,
+    /// The accession number of the filing that reported the value.
+    #[serde(deserialize_with = "deserialize_accession_number")]
+    pub accn: AccessionNumber,
+    /// The fiscal year of the filing that reported the value.
+    pub fy: Option<i32>,
+    /// See [FiscalPeriod]
+    pub fp: Option<FiscalPeriod>,
+    /// The form type of the filing that reported the value, such as `10-K`.
+    pub form: String,
+    /// The date at which the filing that reported the value was made.
+    pub filed: NaiveDate,
+    /// The calendar frame the value was chosen to represent, such as `CY2023Q1I`, if any.
+    pub frame: Option<String>,
+}
+
+impl Fact {
+    /// Whether the value was reported in a filing of `filing_type`. Amendments, such as `10-K/A`, do not match.
+    pub fn is_form(&self, filing_type: FilingTypeOption) -> bool {
+        self.form == filing::to_string(filing_type)
+    }
+}
+
+/// The APIs serve the CIK as a number, while the rest of the crate uses the form with leading zeros.
+pub(crate) fn deserialize_cik<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Cik {
+        Number(u64),
+        Text(String),
+    }
+    Ok(match Cik::deserialize(deserializer)? {
+        Cik::Number(cik) => format!("{cik:010}"),
+        Cik::Text(cik) => crate::edgar_query::edgar_query_builder::add_leading_zeros_to_cik(&cik),
+    })
+}
+/// The APIs serve the accession number as a string, while [AccessionNumber] is deserialized from XML elements elsewhere.
+pub(crate) fn deserialize_accession_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<AccessionNumber, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
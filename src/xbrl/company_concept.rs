@@ -0,0 +1,124 @@
+//! This module provides the company concept API, which serves the facts a company reported for a single concept.
+//!
+//! It is much smaller than the [company facts](crate::xbrl::company_facts) of the company when only one metric is needed.
+//! ```
+//! use sec_edgar::{
+//!     edgar::edgar_client,
+//!     edgar_query::filing::FilingTypeOption::_10K,
+//!     xbrl::{company_concept::company_concept, FiscalPeriod, Taxonomy},
+//! };
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let revenues = company_concept(client, "831001", &Taxonomy::UsGaap, "Revenues")
+//!         .await
+//!         .unwrap();
+//!     for fact in revenues.facts("USD", Some(_10K), Some(&FiscalPeriod::FY)) {
+//!         println!("{}: {}", fact.end, fact.val);
+//!     }
+//! }
+//! ```
+
+use crate::client::EdgarClient;
+use crate::edgar_query::{edgar_query_builder::add_leading_zeros_to_cik, filing::FilingTypeOption};
+use crate::error::EDGARError;
+use crate::xbrl::{deserialize_cik, Fact, FiscalPeriod, Taxonomy};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The facts a company reported for a single concept.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyConcept {
+    /// The CIK with leading zeros.
+    #[serde(deserialize_with = "deserialize_cik")]
+    pub cik: String,
+    /// The name of the company.
+    pub entity_name: String,
+    /// See [Taxonomy]
+    pub taxonomy: Taxonomy,
+    /// The tag of the concept, such as `Revenues`.
+    pub tag: String,
+    /// The human-readable name of the concept.
+    pub label: Option<String>,
+    /// The definition of the concept.
+    pub description: Option<String>,
+    /// The facts, keyed by unit, such as `USD`, `shares` or `USD/shares`.
+    #[serde(default)]
+    pub units: BTreeMap<String, Vec<Fact>>,
+}
+impl CompanyConcept {
+    /// The facts in `unit`, optionally only those reported in filings of `filing_type` and for `fiscal_period`.
+    pub fn facts(
+        &self,
+        unit: &str,
+        filing_type: Option<FilingTypeOption>,
+        fiscal_period: Option<&FiscalPeriod>,
+    ) -> Vec<&Fact> {
+        self.units
+            .get(unit)
+            .map(|facts| {
+                facts
+                    .iter()
+                    .filter(|f| filing_type.is_none_or(|t| f.is_form(t)))
+                    .filter(|f| fiscal_period.is_none_or(|p| f.fp.as_ref() == Some(p)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Get the facts the company with `cik` reported for the concept `tag` of `taxonomy`.
+/// Leading zeros are added to the CIK if needed.
+pub async fn company_concept(
+    client: EdgarClient,
+    cik: &str,
+    taxonomy: &Taxonomy,
+    tag: &str,
+) -> Result<CompanyConcept, EDGARError> {
+    let cik = add_leading_zeros_to_cik(cik);
+    let url = client.endpoints().data_url(&format!(
+        "api/xbrl/companyconcept/CIK{cik}/{taxonomy}/{tag}.json"
+    ))?;
+    client.get_json(&url).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+    use crate::edgar_query::filing::FilingTypeOption::{_10K, _10Q};
+
+    async fn revenues() -> CompanyConcept {
+        let server = TestServer::start().route(
+            "/api/xbrl/companyconcept/CIK0000831001/us-gaap/Revenues.json",
+            vec![TestResponse::ok(
+                "application/json",
+                include_bytes!(
+                    "../../tests/fixtures/xbrl/companyconcept_CIK0000831001_us-gaap_Revenues.json"
+                ),
+            )],
+        );
+        let client = client(&server);
+        company_concept(client, "831001", &Taxonomy::UsGaap, "Revenues")
+            .await
+            .unwrap()
+    }
+    #[tokio::test]
+    async fn company_concept_from_endpoint() {
+        let concept = revenues().await;
+        assert_eq!(concept.cik, "0000831001");
+        assert_eq!(concept.taxonomy, Taxonomy::UsGaap);
+        assert_eq!(concept.tag, "Revenues");
+        assert_eq!(concept.facts("USD", None, None).len(), 5);
+        assert!(concept.facts("EUR", None, None).is_empty())
+    }
+    #[tokio::test]
+    async fn company_concept_filter_facts() {
+        let concept = revenues().await;
+        assert_eq!(concept.facts("USD", Some(_10K), None).len(), 2);
+        let q2 = concept.facts("USD", Some(_10Q), Some(&FiscalPeriod::Q2));
+        assert_eq!(q2.len(), 1);
+        assert_eq!(q2[0].accn.value, "0000831001-23-000114");
+        assert_eq!(concept.facts("USD", None, Some(&FiscalPeriod::Q2)).len(), 2)
+    }
+}
@@ -0,0 +1,110 @@
+//! This module provides the company facts API, which serves every fact a company reported, across every concept and taxonomy.
+//! ```
+//! use sec_edgar::{
+//!     edgar::edgar_client,
+//!     xbrl::{company_facts::company_facts, Taxonomy},
+//! };
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let facts = company_facts(client, "831001").await.unwrap();
+//!     let revenues = facts.concept(&Taxonomy::UsGaap, "Revenues").unwrap();
+//!     for fact in revenues.units.get("USD").unwrap() {
+//!         println!("{}: {}", fact.end, fact.val);
+//!     }
+//! }
+//! ```
+
+use crate::client::EdgarClient;
+use crate::edgar_query::edgar_query_builder::add_leading_zeros_to_cik;
+use crate::error::EDGARError;
+use crate::xbrl::{deserialize_cik, Fact, Taxonomy};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Every fact reported by a company, grouped by taxonomy and concept.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyFacts {
+    /// The CIK with leading zeros.
+    #[serde(deserialize_with = "deserialize_cik")]
+    pub cik: String,
+    /// The name of the company.
+    pub entity_name: String,
+    /// The concepts of each taxonomy, keyed by taxonomy name and then by concept tag, such as `us-gaap` and `Revenues`.
+    #[serde(default)]
+    pub facts: BTreeMap<String, BTreeMap<String, Concept>>,
+}
+/// A concept, such as `Revenues`, and the facts reported for it.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Concept {
+    /// The human-readable name of the concept.
+    pub label: Option<String>,
+    /// The definition of the concept.
+    pub description: Option<String>,
+    /// The facts, keyed by unit, such as `USD`, `shares` or `USD/shares`.
+    #[serde(default)]
+    pub units: BTreeMap<String, Vec<Fact>>,
+}
+impl CompanyFacts {
+    /// The concepts of `taxonomy`, keyed by tag.
+    pub fn taxonomy(&self, taxonomy: &Taxonomy) -> Option<&BTreeMap<String, Concept>> {
+        self.facts.get(taxonomy.as_str())
+    }
+    /// The concept `tag` of `taxonomy`.
+    pub fn concept(&self, taxonomy: &Taxonomy, tag: &str) -> Option<&Concept> {
+        self.taxonomy(taxonomy)?.get(tag)
+    }
+    /// The taxonomies the company reported facts in.
+    pub fn taxonomies(&self) -> Vec<Taxonomy> {
+        self.facts
+            .keys()
+            .map(|t| Taxonomy::from(t.as_str()))
+            .collect()
+    }
+}
+
+/// Get every fact reported by the company with `cik`.
+/// Leading zeros are added to the CIK if needed.
+pub async fn company_facts(client: EdgarClient, cik: &str) -> Result<CompanyFacts, EDGARError> {
+    let cik = add_leading_zeros_to_cik(cik);
+    let url = client
+        .endpoints()
+        .data_url(&format!("api/xbrl/companyfacts/CIK{cik}.json"))?;
+    client.get_json(&url).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+    use crate::xbrl::FiscalPeriod;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn company_facts_from_endpoint() {
+        let server = TestServer::start().route(
+            "/api/xbrl/companyfacts/CIK0000831001.json",
+            vec![TestResponse::ok(
+                "application/json",
+                include_bytes!("../../tests/fixtures/xbrl/companyfacts_CIK0000831001.json"),
+            )],
+        );
+        let client = client(&server);
+        let facts = company_facts(client, "831001").await.unwrap();
+        assert_eq!(facts.cik, "0000831001");
+        assert_eq!(facts.entity_name, "CITIGROUP INC");
+        assert_eq!(facts.taxonomies(), vec![Taxonomy::Dei, Taxonomy::UsGaap]);
+        let revenues = &facts.concept(&Taxonomy::UsGaap, "Revenues").unwrap().units["USD"];
+        assert_eq!(revenues.len(), 3);
+        assert_eq!(revenues[0].start, NaiveDate::from_ymd_opt(2022, 1, 1));
+        assert_eq!(revenues[0].val, 75338000000.0);
+        assert_eq!(revenues[0].accn.filer_id, "0000831001");
+        assert_eq!(revenues[0].fp, Some(FiscalPeriod::FY));
+        assert_eq!(revenues[2].frame, None);
+        let shares = &facts
+            .concept(&Taxonomy::Dei, "EntityCommonStockSharesOutstanding")
+            .unwrap()
+            .units["shares"];
+        assert_eq!(shares[0].start, None)
+    }
+}
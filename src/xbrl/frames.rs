@@ -0,0 +1,273 @@
+//! This module provides the frames API, which serves one fact per filer for a concept, a unit and a calendar period.
+//!
+//! Each fact is the one that best fits the calendar period, which makes it possible to compare a metric across every filer.
+//! ```
+//! use sec_edgar::{
+//!     edgar::edgar_client,
+//!     xbrl::{frames::{frames, FramePeriod}, Taxonomy},
+//! };
+//! async fn some_func() {
+//!     let client = edgar_client().unwrap();
+//!     let period: FramePeriod = "CY2023Q1".parse().unwrap();
+//!     let frame = frames(client, &Taxonomy::UsGaap, "Revenues", "USD", &period)
+//!         .await
+//!         .unwrap();
+//!     for fact in frame.data {
+//!         println!("{}: {}", fact.entity_name, fact.val);
+//!     }
+//! }
+//! ```
+
+use crate::client::EdgarClient;
+use crate::edgar_query::filing_content_value::AccessionNumber;
+use crate::error::EDGARError;
+use crate::fields::invalid_field;
+use crate::xbrl::{deserialize_accession_number, deserialize_cik, Taxonomy};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// A calendar period of the frames API.
+/// ```
+/// use sec_edgar::xbrl::frames::FramePeriod;
+/// assert_eq!(FramePeriod::annual(2023).to_string(), "CY2023");
+/// assert_eq!(FramePeriod::quarterly(2023, 1).unwrap().to_string(), "CY2023Q1");
+/// assert_eq!(FramePeriod::instant(2023, 1).unwrap().to_string(), "CY2023Q1I");
+/// assert!("CY2023I".parse::<FramePeriod>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "String")]
+pub enum FramePeriod {
+    /// Facts that cover a calendar year, give or take 30 days, written as `CY2023`.
+    Annual {
+        /// The calendar year.
+        year: u16,
+    },
+    /// Facts that cover a calendar quarter, give or take 30 days, written as `CY2023Q1`.
+    Quarterly {
+        /// The calendar year.
+        year: u16,
+        /// The quarter, from 1 to 4.
+        quarter: u8,
+    },
+    /// Facts at an instant, such as balances, as of the end of a calendar quarter, written as `CY2023Q1I`.
+    Instant {
+        /// The calendar year.
+        year: u16,
+        /// The quarter, from 1 to 4.
+        quarter: u8,
+    },
+}
+impl FramePeriod {
+    /// The calendar year `year`.
+    pub fn annual(year: u16) -> Self {
+        Self::Annual { year }
+    }
+    /// The `quarter` of `year`. The quarter must be from 1 to 4.
+    pub fn quarterly(year: u16, quarter: u8) -> Result<Self, EDGARError> {
+        Ok(Self::Quarterly {
+            year,
+            quarter: validate_quarter(quarter)?,
+        })
+    }
+    /// The end of the `quarter` of `year`. The quarter must be from 1 to 4.
+    pub fn instant(year: u16, quarter: u8) -> Result<Self, EDGARError> {
+        Ok(Self::Instant {
+            year,
+            quarter: validate_quarter(quarter)?,
+        })
+    }
+}
+impl FromStr for FramePeriod {
+    type Err = EDGARError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || invalid_field("frame period", s);
+        let rest = s.strip_prefix("CY").ok_or_else(invalid)?;
+        let year = rest.get(..4).ok_or_else(invalid)?;
+        if !year.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let year = year.parse().map_err(|_| invalid())?;
+        match &rest[4..] {
+            "" => Ok(Self::annual(year)),
+            quarter => {
+                let quarter = quarter.strip_prefix('Q').ok_or_else(invalid)?;
+                let (quarter, instant) = match quarter.strip_suffix('I') {
+                    Some(q) => (q, true),
+                    None => (quarter, false),
+                };
+                if quarter.len() != 1 {
+                    return Err(invalid());
+                }
+                let quarter = quarter.parse().map_err(|_| invalid())?;
+                match instant {
+                    true => Self::instant(year, quarter),
+                    false => Self::quarterly(year, quarter),
+                }
+                .map_err(|_| invalid())
+            }
+        }
+    }
+}
+impl TryFrom<String> for FramePeriod {
+    type Error = EDGARError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+impl Display for FramePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Annual { year } => write!(f, "CY{year}"),
+            Self::Quarterly { year, quarter } => write!(f, "CY{year}Q{quarter}"),
+            Self::Instant { year, quarter } => write!(f, "CY{year}Q{quarter}I"),
+        }
+    }
+}
+fn validate_quarter(quarter: u8) -> Result<u8, EDGARError> {
+    match quarter {
+        1..=4 => Ok(quarter),
+        _ => Err(invalid_field("quarter", &quarter.to_string())),
+    }
+}
+
+/// One fact per filer for a concept, a unit and a calendar period.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Frame {
+    /// See [Taxonomy]
+    pub taxonomy: Taxonomy,
+    /// The tag of the concept, such as `Revenues`.
+    pub tag: String,
+    /// The calendar period. See [FramePeriod]
+    pub ccp: FramePeriod,
+    /// The unit of measure, such as `USD`.
+    pub uom: String,
+    /// The human-readable name of the concept.
+    pub label: Option<String>,
+    /// The definition of the concept.
+    pub description: Option<String>,
+    /// The number of facts in the frame.
+    pub pts: u32,
+    /// See [FrameFact]
+    #[serde(default)]
+    pub data: Vec<FrameFact>,
+}
+/// The fact of a filer in a [Frame].
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameFact {
+    /// The accession number of the filing that reported the value.
+    #[serde(deserialize_with = "deserialize_accession_number")]
+    pub accn: AccessionNumber,
+    /// The CIK of the filer with leading zeros.
+    #[serde(deserialize_with = "deserialize_cik")]
+    pub cik: String,
+    /// The name of the filer.
+    pub entity_name: String,
+    /// The location of the filer, such as `US-NY`.
+    pub loc: Option<String>,
+    /// The start of the period the value covers. None for instant frames.
+    pub start: Option<NaiveDate>,
+    /// The end of the period, or the instant, the value covers.
+    pub end: NaiveDate,
+    /// The value.
+    pub val: f64,
+}
+
+/// Get the fact of every filer for the concept `tag` of `taxonomy`, in `unit`, that best fits `period`.
+///
+/// Compound units may be written as in company facts, such as `USD/shares`, or as in the frames API, such as `USD-per-shares`.
+pub async fn frames(
+    client: EdgarClient,
+    taxonomy: &Taxonomy,
+    tag: &str,
+    unit: &str,
+    period: &FramePeriod,
+) -> Result<Frame, EDGARError> {
+    let unit = unit.replace('/', "-per-");
+    let url = client.endpoints().data_url(&format!(
+        "api/xbrl/frames/{taxonomy}/{tag}/{unit}/{period}.json"
+    ))?;
+    client.get_json(&url).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::{client, TestResponse, TestServer};
+
+    #[test]
+    fn frames_period_from_str() {
+        assert_eq!(
+            "CY2023Q1I".parse::<FramePeriod>().unwrap(),
+            FramePeriod::Instant {
+                year: 2023,
+                quarter: 1
+            }
+        );
+        assert_eq!(
+            "CY2019".parse::<FramePeriod>().unwrap(),
+            FramePeriod::annual(2019)
+        );
+        for invalid in [
+            "2023",
+            "CY23",
+            "CY2023Q5",
+            "CY2023Q",
+            "CY2023Q12",
+            "CY2023I",
+            "cy2023",
+            "CYé2023",
+        ] {
+            assert!(invalid.parse::<FramePeriod>().is_err(), "{invalid}")
+        }
+    }
+    #[tokio::test]
+    async fn frames_from_endpoint() {
+        let server = TestServer::start().route(
+            "/api/xbrl/frames/us-gaap/Revenues/USD/CY2023Q1.json",
+            vec![TestResponse::ok(
+                "application/json",
+                include_bytes!(
+                    "../../tests/fixtures/xbrl/frames_us-gaap_Revenues_USD_CY2023Q1.json"
+                ),
+            )],
+        );
+        let client = client(&server);
+        let period = FramePeriod::quarterly(2023, 1).unwrap();
+        let frame = frames(client, &Taxonomy::UsGaap, "Revenues", "USD", &period)
+            .await
+            .unwrap();
+        assert_eq!(frame.ccp, period);
+        assert_eq!(frame.pts, 2);
+        assert_eq!(frame.data[1].cik, "0000070858");
+        assert_eq!(frame.data[1].entity_name, "BANK OF AMERICA CORP /DE/");
+        assert_eq!(frame.data[1].accn.filer_id, frame.data[1].cik);
+        assert_eq!(frame.data[0].val, 21447000000.0)
+    }
+    #[tokio::test]
+    async fn frames_compound_unit() {
+        let frame = r#"{"taxonomy":"us-gaap","tag":"EarningsPerShareBasic","ccp":"CY2023Q1","uom":"USD-per-shares","label":"Earnings Per Share, Basic","description":"","pts":1,"data":[{"accn":"0000831001-23-000089","cik":831001,"entityName":"CITIGROUP INC","loc":"US-NY","start":"2023-01-01","end":"2023-03-31","val":2.21}]}"#;
+        let server = TestServer::start().route(
+            "/api/xbrl/frames/us-gaap/EarningsPerShareBasic/USD-per-shares/CY2023Q1.json",
+            vec![TestResponse::ok("application/json", frame.as_bytes())],
+        );
+        let client = client(&server);
+        let period = FramePeriod::quarterly(2023, 1).unwrap();
+        for unit in ["USD/shares", "USD-per-shares"] {
+            let frame = frames(
+                client.clone(),
+                &Taxonomy::UsGaap,
+                "EarningsPerShareBasic",
+                unit,
+                &period,
+            )
+            .await
+            .unwrap();
+            assert_eq!(frame.data[0].val, 2.21)
+        }
+    }
+}